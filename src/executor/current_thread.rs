@@ -0,0 +1,259 @@
+//! An executor which drives any number of futures on the current thread.
+//!
+//! Unlike `Spawn::execute`, the futures managed by `CurrentThread` are never
+//! moved to another thread, so they're not required to be `Send`. This makes
+//! it possible to run futures which hold onto `Rc` and `RefCell` state, for
+//! example state shared with a GUI toolkit which lives on the main thread.
+
+use std::prelude::v1::*;
+
+use std::cell::RefCell;
+use std::mem;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::SeqCst;
+use std::thread;
+
+use {Future, Async};
+use stack::Stack;
+use task_impl::{self, Spawn, Unpark};
+
+type LocalFuture = Box<Future<Item = (), Error = ()>>;
+
+/// An executor which runs futures on the thread which created it.
+///
+/// Futures are added to the executor with the `spawn` method, or through a
+/// `Spawner` handle, and are all driven by the `run` method. Each spawned
+/// future is its own task, and only those tasks which were notified through
+/// `Task::unpark` are polled again.
+///
+/// `CurrentThread` is neither `Send` nor `Sync`; all futures it manages stay
+/// on the thread that created it.
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// use futures::future::{self, Future};
+/// use futures::executor::CurrentThread;
+/// use futures::sync::oneshot;
+///
+/// let hits = Rc::new(Cell::new(0));
+/// let mut executor = CurrentThread::new();
+///
+/// let (tx, rx) = oneshot::channel();
+/// let hits2 = hits.clone();
+/// executor.spawn(future::lazy(move || {
+///     hits2.set(hits2.get() + 1);
+///     tx.complete(());
+///     Ok(())
+/// }));
+///
+/// executor.run(rx).unwrap();
+/// assert_eq!(hits.get(), 1);
+/// ```
+pub struct CurrentThread {
+    // A slab of the spawned tasks, reusing free slots in the same fashion as
+    // `FuturesUnordered`. The `next_task` field is the head of the free list
+    // and equals `tasks.len()` when the slab is full.
+    tasks: Vec<Slot>,
+    next_task: usize,
+
+    // Futures which have been spawned but not yet moved into `tasks`. This is
+    // shared with `Spawner` handles so that running futures can spawn more.
+    incoming: Rc<RefCell<Vec<LocalFuture>>>,
+
+    // Notifications which have arrived for our tasks.
+    ready: Arc<Ready>,
+}
+
+/// A handle through which futures can be spawned onto a `CurrentThread`.
+///
+/// This is created by the `CurrentThread::spawner` method, and can be cloned
+/// and moved into futures running on the executor in order to spawn further
+/// futures. Like `CurrentThread` itself this handle can't leave the thread.
+#[derive(Clone)]
+pub struct Spawner {
+    incoming: Weak<RefCell<Vec<LocalFuture>>>,
+}
+
+enum Slot {
+    Next(usize),
+    Task(Spawn<LocalFuture>, Arc<TaskUnpark>),
+}
+
+struct Ready {
+    // The thread running `CurrentThread::run`, woken up on each notification.
+    thread: thread::Thread,
+
+    // Identifiers of the tasks which have been unparked.
+    tasks: Stack<usize>,
+
+    // Whether the future passed to `run` has been unparked.
+    root: AtomicBool,
+}
+
+struct TaskUnpark {
+    id: usize,
+    ready: Arc<Ready>,
+}
+
+struct RootUnpark {
+    ready: Arc<Ready>,
+}
+
+impl CurrentThread {
+    /// Creates a new executor which will run futures on the current thread.
+    pub fn new() -> CurrentThread {
+        CurrentThread {
+            tasks: Vec::new(),
+            next_task: 0,
+            incoming: Rc::new(RefCell::new(Vec::new())),
+            ready: Arc::new(Ready {
+                thread: thread::current(),
+                tasks: Stack::new(),
+                root: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Spawns a new future onto this executor.
+    ///
+    /// The future will be polled for the first time the next time `run` is
+    /// called (or immediately if `run` is already executing), and will then
+    /// be driven to completion by subsequent calls to `run`. Spawned futures
+    /// are not required to be `Send`.
+    pub fn spawn<F>(&self, future: F)
+        where F: Future<Item = (), Error = ()> + 'static,
+    {
+        self.incoming.borrow_mut().push(Box::new(future));
+    }
+
+    /// Returns a handle which can be used to spawn futures onto this executor
+    /// from within other futures.
+    pub fn spawner(&self) -> Spawner {
+        Spawner {
+            incoming: Rc::downgrade(&self.incoming),
+        }
+    }
+
+    /// Runs this executor until the given future has completed, returning its
+    /// result.
+    ///
+    /// All futures spawned onto this executor are driven alongside `future`,
+    /// blocking the current thread while there's no work to do. Only tasks
+    /// which have been notified since they were last polled are polled again.
+    ///
+    /// Once `future` completes this function returns, even if other spawned
+    /// futures haven't finished yet. They'll continue to make progress on the
+    /// next call to `run`, or be dropped along with the executor.
+    pub fn run<F: Future>(&mut self, future: F) -> Result<F::Item, F::Error> {
+        let mut root = task_impl::spawn(future);
+        let unpark = Arc::new(RootUnpark { ready: self.ready.clone() });
+        self.ready.root.store(true, SeqCst);
+
+        loop {
+            if self.ready.root.swap(false, SeqCst) {
+                if let Async::Ready(e) = try!(root.poll_future(unpark.clone())) {
+                    return Ok(e)
+                }
+            }
+
+            self.spawn_incoming();
+
+            let mut polled = false;
+            for id in self.ready.tasks.drain() {
+                self.poll_task(id);
+                polled = true;
+            }
+
+            // If nothing was ready to run then block until something is
+            // unparked. Notifications which arrived since we last looked will
+            // have already unparked this thread, so `park` returns right away.
+            if !polled && !self.ready.root.load(SeqCst) {
+                thread::park();
+            }
+        }
+    }
+
+    // Moves all newly spawned futures into the slab, scheduling each of them
+    // to be polled.
+    fn spawn_incoming(&mut self) {
+        let incoming = self.incoming.borrow_mut().drain(..).collect::<Vec<_>>();
+        for future in incoming {
+            let id = self.next_task;
+            let unpark = Arc::new(TaskUnpark {
+                id: id,
+                ready: self.ready.clone(),
+            });
+            let task = Slot::Task(task_impl::spawn(future), unpark);
+            if id == self.tasks.len() {
+                self.tasks.push(task);
+                self.next_task += 1;
+            } else {
+                match mem::replace(&mut self.tasks[id], task) {
+                    Slot::Next(next) => self.next_task = next,
+                    Slot::Task(..) => panic!(),
+                }
+            }
+            self.ready.tasks.push(id);
+        }
+    }
+
+    fn poll_task(&mut self, id: usize) {
+        let done = match self.tasks.get_mut(id) {
+            Some(&mut Slot::Task(ref mut spawn, ref unpark)) => {
+                match spawn.poll_future(unpark.clone()) {
+                    Ok(Async::NotReady) => false,
+                    Ok(Async::Ready(())) |
+                    Err(()) => true,
+                }
+            }
+
+            // A stale notification for a task which has since completed.
+            Some(&mut Slot::Next(_)) | None => false,
+        };
+        if done {
+            self.tasks[id] = Slot::Next(self.next_task);
+            self.next_task = id;
+        }
+    }
+}
+
+impl Default for CurrentThread {
+    fn default() -> CurrentThread {
+        CurrentThread::new()
+    }
+}
+
+impl Spawner {
+    /// Spawns a new future onto the `CurrentThread` executor this handle was
+    /// created from.
+    ///
+    /// If the executor has been dropped then the future is dropped
+    /// immediately as well, without being polled.
+    pub fn spawn<F>(&self, future: F)
+        where F: Future<Item = (), Error = ()> + 'static,
+    {
+        if let Some(incoming) = self.incoming.upgrade() {
+            incoming.borrow_mut().push(Box::new(future));
+        }
+    }
+}
+
+impl Unpark for TaskUnpark {
+    fn unpark(&self) {
+        self.ready.tasks.push(self.id);
+        self.ready.thread.unpark();
+    }
+}
+
+impl Unpark for RootUnpark {
+    fn unpark(&self) {
+        self.ready.root.store(true, SeqCst);
+        self.ready.thread.unpark();
+    }
+}
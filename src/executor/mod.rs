@@ -5,3 +5,6 @@
 // TODO: more dox
 
 pub use task_impl::{Spawn, spawn, Unpark, Executor, Run};
+
+mod current_thread;
+pub use self::current_thread::{CurrentThread, Spawner};
@@ -0,0 +1,212 @@
+//! Definition of the `Abortable` combinator, allowing a future or stream to
+//! be aborted remotely.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::SeqCst;
+
+use {Async, Future, Poll};
+use stream::Stream;
+use sync::AtomicTask;
+
+/// A future or stream which can be remotely short-circuited using an
+/// `AbortHandle`.
+///
+/// Created by the `future::abortable` and `stream::abortable` functions, or
+/// with `Abortable::new`.
+#[must_use = "futures do nothing unless polled"]
+pub struct Abortable<T> {
+    inner: T,
+    shared: Arc<AbortInner>,
+}
+
+/// A handle to an `Abortable` future or stream.
+///
+/// Handles can be cloned and moved between threads, and `abort` can be called
+/// from any of them.
+#[derive(Clone, Debug)]
+pub struct AbortHandle {
+    inner: Arc<AbortInner>,
+}
+
+/// A registration handle for an `Abortable` future or stream, which links it
+/// to the `AbortHandle` it was created along with.
+///
+/// Created by `AbortHandle::new_pair`.
+#[derive(Debug)]
+pub struct AbortRegistration {
+    inner: Arc<AbortInner>,
+}
+
+#[derive(Debug)]
+struct AbortInner {
+    task: AtomicTask,
+    aborted: AtomicBool,
+}
+
+/// Error returned from an `Abortable` future or stream, which distinguishes
+/// it being aborted through its `AbortHandle` from an error of the wrapped
+/// future or stream.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AbortError<E> {
+    /// The future or stream was aborted.
+    Aborted,
+
+    /// The wrapped future or stream produced an error.
+    Inner(E),
+}
+
+impl<E> AbortError<E> {
+    /// Returns whether this error was caused by the future or stream being
+    /// aborted.
+    pub fn is_aborted(&self) -> bool {
+        match *self {
+            AbortError::Aborted => true,
+            AbortError::Inner(_) => false,
+        }
+    }
+
+    /// Returns the error of the wrapped future or stream, if that's what
+    /// this error is.
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            AbortError::Aborted => None,
+            AbortError::Inner(e) => Some(e),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for AbortError<E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AbortError::Aborted => write!(fmt, "`Abortable` future has been aborted"),
+            AbortError::Inner(ref e) => e.fmt(fmt),
+        }
+    }
+}
+
+impl<E: Error> Error for AbortError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            AbortError::Aborted => "`Abortable` future has been aborted",
+            AbortError::Inner(_) => "wrapped future failed",
+        }
+    }
+}
+
+/// Creates a new `Abortable` future along with an `AbortHandle` which can be
+/// used to abort it.
+///
+/// Once `AbortHandle::abort` has been called, the future will resolve to
+/// `AbortError::Aborted` the next time it's polled. Errors of the wrapped
+/// future are returned as `AbortError::Inner`.
+///
+/// # Examples
+///
+/// ```
+/// use futures::future::{self, Future, AbortError};
+///
+/// let (future, handle) = future::abortable(future::empty::<(), ()>());
+/// handle.abort();
+/// assert_eq!(future.wait(), Err(AbortError::Aborted));
+/// ```
+pub fn abortable<F: Future>(future: F) -> (Abortable<F>, AbortHandle) {
+    let (handle, reg) = AbortHandle::new_pair();
+    (Abortable::new(future, reg), handle)
+}
+
+impl<T> Abortable<T> {
+    /// Creates a new `Abortable` future or stream wrapping `inner`, which can
+    /// be aborted through the `AbortHandle` linked to `reg`.
+    pub fn new(inner: T, reg: AbortRegistration) -> Abortable<T> {
+        Abortable {
+            inner: inner,
+            shared: reg.inner,
+        }
+    }
+
+    /// Get a shared reference to the inner future or stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner future or stream.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this combinator, returning the underlying future or stream.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns whether `AbortHandle::abort` has been called for this
+    /// future or stream.
+    pub fn is_aborted(&self) -> bool {
+        self.shared.aborted.load(SeqCst)
+    }
+
+    fn try_poll<R, E, F>(&mut self, poll: F) -> Poll<R, AbortError<E>>
+        where F: FnOnce(&mut T) -> Poll<R, E>,
+    {
+        if self.is_aborted() {
+            return Err(AbortError::Aborted)
+        }
+
+        match poll(&mut self.inner) {
+            Ok(Async::Ready(r)) => return Ok(Async::Ready(r)),
+            Ok(Async::NotReady) => {}
+            Err(e) => return Err(AbortError::Inner(e)),
+        }
+
+        // Register after polling, and check again afterwards so an abort
+        // which comes in between isn't missed.
+        self.shared.task.register();
+        if self.is_aborted() {
+            return Err(AbortError::Aborted)
+        }
+        Ok(Async::NotReady)
+    }
+}
+
+impl<F: Future> Future for Abortable<F> {
+    type Item = F::Item;
+    type Error = AbortError<F::Error>;
+
+    fn poll(&mut self) -> Poll<F::Item, AbortError<F::Error>> {
+        self.try_poll(|f| f.poll())
+    }
+}
+
+impl<S: Stream> Stream for Abortable<S> {
+    type Item = S::Item;
+    type Error = AbortError<S::Error>;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, AbortError<S::Error>> {
+        self.try_poll(|s| s.poll())
+    }
+}
+
+impl AbortHandle {
+    /// Creates an `AbortHandle` along with the `AbortRegistration` to pass to
+    /// `Abortable::new`.
+    pub fn new_pair() -> (AbortHandle, AbortRegistration) {
+        let inner = Arc::new(AbortInner {
+            task: AtomicTask::new(),
+            aborted: AtomicBool::new(false),
+        });
+        (AbortHandle { inner: inner.clone() }, AbortRegistration { inner: inner })
+    }
+
+    /// Aborts the linked `Abortable` future or stream.
+    ///
+    /// The task it's running on is woken up, and it will return
+    /// `AbortError::Aborted` the next time it's polled, without polling the wrapped future
+    /// or stream again. Aborting more than once has no further effect.
+    pub fn abort(&self) {
+        self.inner.aborted.store(true, SeqCst);
+        self.inner.task.notify();
+    }
+}
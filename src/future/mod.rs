@@ -10,6 +10,7 @@ mod empty;
 #[path = "err.rs"]  // remove when deprecated reexports are gone
 mod err_;
 mod lazy;
+mod loop_fn;
 #[path = "ok.rs"]
 mod ok_;
 mod poll_fn;
@@ -18,6 +19,7 @@ mod result_;
 pub use self::empty::{empty, Empty};
 pub use self::err_::{err, Err};
 pub use self::lazy::{lazy, Lazy};
+pub use self::loop_fn::{loop_fn, Loop, LoopFn};
 pub use self::ok_::{ok, Ok};
 pub use self::poll_fn::{poll_fn, PollFn};
 pub use self::result_::{result, FutureResult};
@@ -46,7 +48,9 @@ mod map;
 mod map_err;
 mod or_else;
 mod select;
+mod select2;
 mod then;
+mod either;
 
 // impl details
 mod chain;
@@ -61,17 +65,25 @@ pub use self::map::Map;
 pub use self::map_err::MapErr;
 pub use self::or_else::OrElse;
 pub use self::select::{Select, SelectNext};
+pub use self::select2::Select2;
 pub use self::then::Then;
+pub use self::either::Either;
 
 if_std! {
+    mod abortable;
     mod catch_unwind;
     mod join_all;
     mod select_all;
     mod select_ok;
+    mod shared;
+    mod with_cancellation;
+    pub use self::abortable::{abortable, Abortable, AbortHandle, AbortRegistration, AbortError};
     pub use self::catch_unwind::CatchUnwind;
     pub use self::join_all::{join_all, JoinAll};
     pub use self::select_all::{SelectAll, SelectAllNext, select_all};
     pub use self::select_ok::{SelectOk, select_ok};
+    pub use self::shared::{Shared, SharedItem, SharedError};
+    pub use self::with_cancellation::{WithCancellation, CancellationError};
 
     #[doc(hidden)]
     #[deprecated(since = "0.1.4", note = "use join_all instead")]
@@ -489,6 +501,50 @@ pub trait Future {
                         (Self::Error, SelectNext<Self, B::Future>), _>(f)
     }
 
+    /// Waits for either one of two differently-typed futures to complete.
+    ///
+    /// This function will return a new future which awaits for either this or
+    /// the `other` future to complete. The returned future will finish with
+    /// both the value resolved and a future representing the completion of the
+    /// other work.
+    ///
+    /// Unlike `select`, the two futures may have different item and error
+    /// types. The result is an `Either` which records which future finished
+    /// first, alongside the other future with its original type preserved.
+    ///
+    /// Note that this function consumes the receiving future and returns a
+    /// wrapped version of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::future::*;
+    ///
+    /// // A poor-man's join implemented on top of select2
+    ///
+    /// fn join<A, B, E>(a: A, b: B) -> BoxFuture<(A::Item, B::Item), E>
+    ///     where A: Future<Error = E> + Send + 'static,
+    ///           B: Future<Error = E> + Send + 'static,
+    ///           A::Item: Send + 'static,
+    ///           B::Item: Send + 'static,
+    ///           E: Send + 'static,
+    /// {
+    ///     a.select2(b).then(|res| {
+    ///         match res {
+    ///             Ok(Either::A((x, b))) => b.map(move |y| (x, y)).boxed(),
+    ///             Ok(Either::B((y, a))) => a.map(move |x| (x, y)).boxed(),
+    ///             Err(Either::A((e, _))) => err(e).boxed(),
+    ///             Err(Either::B((e, _))) => err(e).boxed(),
+    ///         }
+    ///     }).boxed()
+    /// }
+    /// ```
+    fn select2<B>(self, other: B) -> Select2<Self, B::Future>
+        where B: IntoFuture, Self: Sized
+    {
+        select2::new(self, other.into_future())
+    }
+
     /// Joins the result of two futures, waiting for them both to complete.
     ///
     /// This function will return a new future which awaits both this and the
@@ -729,6 +785,121 @@ pub trait Future {
     {
             catch_unwind::new(self)
         }
+
+    /// Create a cloneable handle to this future where all handles will resolve
+    /// to the same result.
+    ///
+    /// The shared() method provides a method to convert any future into a
+    /// cloneable future. It enables a future to be polled by multiple threads.
+    ///
+    /// The returned `Shared` future resolves successfully with
+    /// `SharedItem<Self::Item>` or erroneously with `SharedError<Self::Error>`.
+    /// Both `SharedItem` and `SharedError` implements `Deref` to allow shared
+    /// access to the underlying result. Ownership of `Self::Item` and
+    /// `Self::Error` cannot currently be reclaimed.
+    ///
+    /// Every task that polls a clone is recorded, and all of them are unparked
+    /// whenever the original future makes progress, regardless of which clone
+    /// happens to be driving it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::future::*;
+    ///
+    /// let future = ok::<_, bool>(6);
+    /// let shared1 = future.shared();
+    /// let shared2 = shared1.clone();
+    /// assert_eq!(6, *shared1.wait().unwrap());
+    /// assert_eq!(6, *shared2.wait().unwrap());
+    /// ```
+    ///
+    /// ```
+    /// use std::thread;
+    /// use futures::future::*;
+    ///
+    /// let future = ok::<_, bool>(6);
+    /// let shared1 = future.shared();
+    /// let shared2 = shared1.clone();
+    /// let join_handle = thread::spawn(move || {
+    ///     assert_eq!(6, *shared2.wait().unwrap());
+    /// });
+    /// assert_eq!(6, *shared1.wait().unwrap());
+    /// join_handle.join().unwrap();
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn shared(self) -> Shared<Self>
+        where Self: Sized
+    {
+        shared::new(self)
+    }
+
+    /// Resolve this future early if `token` is cancelled.
+    ///
+    /// The returned future resolves to the same value as this future, unless
+    /// the token (or one of its ancestors) is cancelled first, in which case
+    /// it fails with `CancellationError::Cancelled` and this future is
+    /// dropped without being polled again. Errors of this future are wrapped
+    /// in `CancellationError::Inner`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::future::{self, Future, CancellationError};
+    /// use futures::sync::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// let future = future::empty::<(), ()>().with_cancellation(&token);
+    /// token.cancel();
+    /// assert_eq!(future.wait(), Err(CancellationError::Cancelled));
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn with_cancellation(self, token: &::sync::CancellationToken) -> WithCancellation<Self>
+        where Self: Sized
+    {
+        with_cancellation::new(self, token)
+    }
+
+    /// Fail this future with `TimeoutError::Elapsed` unless it completes
+    /// within `duration`.
+    ///
+    /// The returned future resolves to the same value as this future if it
+    /// completes in time. Otherwise this future is dropped, without being
+    /// polled again, and the returned future fails with
+    /// `TimeoutError::Elapsed`. Errors of this future are wrapped in
+    /// `TimeoutError::Inner`.
+    ///
+    /// The timeout is tracked by the default timer, see the `timer` module.
+    /// Use `Timer::timeout` to use a different timer instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use futures::future::{self, Future};
+    /// use futures::timer::TimeoutError;
+    ///
+    /// let future = future::empty::<(), ()>().timeout(Duration::from_millis(10));
+    /// assert_eq!(future.wait(), Err(TimeoutError::Elapsed));
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn timeout(self, duration: ::std::time::Duration) -> ::timer::Timeout<Self>
+        where Self: Sized
+    {
+        ::timer::timeout(self, duration)
+    }
+
+    /// Fail this future with `TimeoutError::Elapsed` unless it completes
+    /// before `deadline`.
+    ///
+    /// This behaves like `timeout`, except that the point in time at which
+    /// the future is abandoned is given directly.
+    #[cfg(feature = "use_std")]
+    fn deadline(self, deadline: ::std::time::Instant) -> ::timer::Timeout<Self>
+        where Self: Sized
+    {
+        ::timer::deadline(self, deadline)
+    }
 }
 
 impl<'a, F: ?Sized + Future> Future for &'a mut F {
@@ -0,0 +1,287 @@
+//! Definition of the Shared combinator, a future that is cloneable,
+//! and can be polled in multiple threads.
+//!
+//! # Examples
+//!
+//! ```
+//! use futures::future::*;
+//!
+//! let future = ok::<_, bool>(6);
+//! let shared1 = future.shared();
+//! let shared2 = shared1.clone();
+//! assert_eq!(6, *shared1.wait().unwrap());
+//! assert_eq!(6, *shared2.wait().unwrap());
+//! ```
+
+use std::prelude::v1::*;
+
+use std::cell::UnsafeCell;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::SeqCst;
+use std::thread;
+
+use {Future, Poll, Async};
+use executor::{self, Spawn, Unpark};
+use task::{self, Task};
+
+/// A future that is cloneable and can be polled in multiple threads.
+/// Use `Future::shared()` method to convert any future into a `Shared` future.
+#[must_use = "futures do nothing unless polled"]
+pub struct Shared<F: Future> {
+    inner: Arc<Inner<F>>,
+    waiter: usize,
+}
+
+struct Inner<F: Future> {
+    // Source of the ids handed out to each clone of a `Shared`, used as the
+    // key in the `waiters` map of the notifier below.
+    next_clone_id: AtomicUsize,
+
+    // The original future, along with the task it runs in. Only accessible
+    // while in the `POLLING` or `REPOLL` states, and emptied once the future
+    // completes.
+    future: UnsafeCell<Option<Spawn<F>>>,
+
+    // The result of the original future, filled in only once the state is
+    // `COMPLETE`.
+    result: UnsafeCell<Option<SharedResult<F::Item, F::Error>>>,
+
+    notifier: Arc<Notifier>,
+}
+
+type SharedResult<T, E> = Result<SharedItem<T>, SharedError<E>>;
+
+struct Notifier {
+    state: AtomicUsize,
+    waiters: Mutex<HashMap<usize, Task>>,
+}
+
+// Nobody is polling the original future at the moment.
+const IDLE: usize = 0;
+
+// Some clone is polling the original future right now.
+const POLLING: usize = 1;
+
+// Some clone is polling the original future, but a notification arrived in
+// the meantime so it must be polled again before the lock is released.
+const REPOLL: usize = 2;
+
+// The original future has completed and `result` is filled in.
+const COMPLETE: usize = 3;
+
+// The original future panicked while being polled.
+const POISONED: usize = 4;
+
+pub fn new<F: Future>(future: F) -> Shared<F> {
+    Shared {
+        inner: Arc::new(Inner {
+            next_clone_id: AtomicUsize::new(1),
+            notifier: Arc::new(Notifier {
+                state: AtomicUsize::new(IDLE),
+                waiters: Mutex::new(HashMap::new()),
+            }),
+            future: UnsafeCell::new(Some(executor::spawn(future))),
+            result: UnsafeCell::new(None),
+        }),
+        waiter: 0,
+    }
+}
+
+impl<F> Shared<F> where F: Future {
+    fn set_waiter(&mut self) {
+        let task = task::park();
+        self.inner.notifier.waiters.lock().unwrap().insert(self.waiter, task);
+    }
+
+    unsafe fn clone_result(&self) -> Poll<SharedItem<F::Item>, SharedError<F::Error>> {
+        match *self.inner.result.get() {
+            Some(Ok(ref item)) => Ok(Async::Ready(SharedItem { item: item.item.clone() })),
+            Some(Err(ref e)) => Err(SharedError { error: e.error.clone() }),
+            None => panic!("shared future is complete but has no result"),
+        }
+    }
+}
+
+impl<F> Future for Shared<F>
+    where F: Future
+{
+    type Item = SharedItem<F::Item>;
+    type Error = SharedError<F::Error>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        // Register ourselves first so that a notification sent while some
+        // other clone holds the lock is guaranteed to reach this task.
+        self.set_waiter();
+
+        match self.inner.notifier.state.compare_exchange(IDLE, POLLING, SeqCst, SeqCst) {
+            // We've acquired the lock, and are responsible for polling.
+            Ok(_) => {}
+
+            // Another clone is currently polling, and will arrange for us to
+            // be notified once the original future makes progress.
+            Err(POLLING) |
+            Err(REPOLL) => return Ok(Async::NotReady),
+
+            Err(COMPLETE) => return unsafe { self.clone_result() },
+
+            Err(POISONED) => panic!("inner future panicked during poll"),
+
+            Err(_) => unreachable!(),
+        }
+
+        // If the original future panics while we're polling it, flag the
+        // shared state as poisoned and wake up the remaining clones so they
+        // don't wait forever.
+        struct Reset<'a>(&'a Notifier);
+
+        impl<'a> Drop for Reset<'a> {
+            fn drop(&mut self) {
+                if thread::panicking() {
+                    self.0.state.store(POISONED, SeqCst);
+                    self.0.unpark();
+                }
+            }
+        }
+
+        let _reset = Reset(&self.inner.notifier);
+
+        let result;
+        loop {
+            let res = unsafe {
+                (*self.inner.future.get()).as_mut().unwrap()
+                    .poll_future(self.inner.notifier.clone())
+            };
+            match res {
+                Ok(Async::NotReady) => {
+                    match self.inner.notifier.state.compare_exchange(POLLING, IDLE, SeqCst, SeqCst) {
+                        Ok(_) => return Ok(Async::NotReady),
+                        Err(REPOLL) => {
+                            // A notification arrived while we were polling,
+                            // so go around again before giving up the lock.
+                            self.inner.notifier.state.store(POLLING, SeqCst);
+                        }
+                        Err(_) => unreachable!(),
+                    }
+                }
+                Ok(Async::Ready(item)) => {
+                    result = Ok(SharedItem { item: Arc::new(item) });
+                    break
+                }
+                Err(error) => {
+                    result = Err(SharedError { error: Arc::new(error) });
+                    break
+                }
+            }
+        }
+
+        unsafe {
+            *self.inner.result.get() = Some(result);
+            *self.inner.future.get() = None;
+        }
+
+        // Publish the result and wake up everyone else waiting on it. We've
+        // got the result in hand, so there's no need to wake ourselves.
+        self.inner.notifier.waiters.lock().unwrap().remove(&self.waiter);
+        self.inner.notifier.state.store(COMPLETE, SeqCst);
+        self.inner.notifier.unpark();
+
+        unsafe { self.clone_result() }
+    }
+}
+
+impl<F> Clone for Shared<F> where F: Future {
+    fn clone(&self) -> Self {
+        let next_clone_id = self.inner.next_clone_id.fetch_add(1, SeqCst);
+
+        Shared {
+            inner: self.inner.clone(),
+            waiter: next_clone_id,
+        }
+    }
+}
+
+impl<F> Drop for Shared<F> where F: Future {
+    fn drop(&mut self) {
+        let mut waiters = self.inner.notifier.waiters.lock().unwrap();
+        waiters.remove(&self.waiter);
+    }
+}
+
+impl Unpark for Notifier {
+    fn unpark(&self) {
+        // If some clone is polling the original future right now, make sure
+        // it polls again before releasing the lock.
+        let _ = self.state.compare_exchange(POLLING, REPOLL, SeqCst, SeqCst);
+
+        // Don't hold the lock while unparking, the tasks may well be polled
+        // right away on another thread and want to register themselves again.
+        let waiters = self.waiters.lock().unwrap()
+                          .drain()
+                          .map(|(_, task)| task)
+                          .collect::<Vec<_>>();
+
+        for waiter in waiters {
+            waiter.unpark();
+        }
+    }
+}
+
+// The `future` and `result` cells are only ever touched by the single clone
+// that holds the polling lock, and `result` is read-only once `COMPLETE` is
+// published, so `Inner` is shareable as long as the pieces themselves are.
+unsafe impl<F> Sync for Inner<F>
+    where F: Future + Send,
+          F::Item: Send + Sync,
+          F::Error: Send + Sync,
+{}
+
+unsafe impl<F> Send for Inner<F>
+    where F: Future + Send,
+          F::Item: Send + Sync,
+          F::Error: Send + Sync,
+{}
+
+/// A wrapped item of the original future that is cloneable and implements
+/// Deref for ease of use.
+#[derive(Debug)]
+pub struct SharedItem<T> {
+    item: Arc<T>,
+}
+
+impl<T> Clone for SharedItem<T> {
+    fn clone(&self) -> Self {
+        SharedItem { item: self.item.clone() }
+    }
+}
+
+impl<T> Deref for SharedItem<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.item
+    }
+}
+
+/// A wrapped error of the original future that is cloneable and implements
+/// Deref for ease of use.
+#[derive(Debug)]
+pub struct SharedError<E> {
+    error: Arc<E>,
+}
+
+impl<E> Clone for SharedError<E> {
+    fn clone(&self) -> Self {
+        SharedError { error: self.error.clone() }
+    }
+}
+
+impl<E> Deref for SharedError<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.error
+    }
+}
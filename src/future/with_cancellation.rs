@@ -0,0 +1,109 @@
+//! Definition of the `WithCancellation` combinator, resolving a future early
+//! once a `CancellationToken` is cancelled.
+
+use std::error::Error;
+use std::fmt;
+
+use {Async, Future, Poll};
+use sync::{CancellationToken, WaitForCancellation};
+
+/// Future for the `with_cancellation` combinator, which resolves early with
+/// an error once a `CancellationToken` is cancelled.
+///
+/// This is created by the `Future::with_cancellation` method.
+#[must_use = "futures do nothing unless polled"]
+pub struct WithCancellation<F> {
+    future: Option<F>,
+    cancelled: WaitForCancellation,
+}
+
+/// Error returned from a `WithCancellation` future, which distinguishes the
+/// token being cancelled from an error of the wrapped future.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CancellationError<E> {
+    /// The token was cancelled before the wrapped future resolved.
+    Cancelled,
+
+    /// The wrapped future resolved to an error.
+    Inner(E),
+}
+
+impl<E> CancellationError<E> {
+    /// Returns whether this error was caused by the token being cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        match *self {
+            CancellationError::Cancelled => true,
+            CancellationError::Inner(_) => false,
+        }
+    }
+
+    /// Returns the error of the wrapped future, if that's what this error
+    /// is.
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            CancellationError::Cancelled => None,
+            CancellationError::Inner(e) => Some(e),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for CancellationError<E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CancellationError::Cancelled => write!(fmt, "future was cancelled"),
+            CancellationError::Inner(ref e) => e.fmt(fmt),
+        }
+    }
+}
+
+impl<E: Error> Error for CancellationError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            CancellationError::Cancelled => "future was cancelled",
+            CancellationError::Inner(_) => "wrapped future failed",
+        }
+    }
+}
+
+pub fn new<F: Future>(future: F, token: &CancellationToken) -> WithCancellation<F> {
+    WithCancellation {
+        future: Some(future),
+        cancelled: token.cancelled(),
+    }
+}
+
+impl<F> WithCancellation<F> {
+    /// Get a shared reference to the inner future, if it hasn't been dropped
+    /// because the token was cancelled.
+    pub fn get_ref(&self) -> Option<&F> {
+        self.future.as_ref()
+    }
+
+    /// Get a mutable reference to the inner future, if it hasn't been
+    /// dropped because the token was cancelled.
+    pub fn get_mut(&mut self) -> Option<&mut F> {
+        self.future.as_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying future if it
+    /// hasn't been dropped because the token was cancelled.
+    pub fn into_inner(self) -> Option<F> {
+        self.future
+    }
+}
+
+impl<F: Future> Future for WithCancellation<F> {
+    type Item = F::Item;
+    type Error = CancellationError<F::Error>;
+
+    fn poll(&mut self) -> Poll<F::Item, CancellationError<F::Error>> {
+        if let Ok(Async::Ready(())) = self.cancelled.poll() {
+            self.future = None;
+            return Err(CancellationError::Cancelled)
+        }
+        self.future.as_mut()
+            .expect("cannot poll WithCancellation after it has been cancelled")
+            .poll()
+            .map_err(CancellationError::Inner)
+    }
+}
@@ -179,6 +179,9 @@ pub use stream::Stream;
 pub mod sink;
 pub use sink::Sink;
 
+mod never;
+pub use never::Never;
+
 #[deprecated(since = "0.1.4", note = "import through the future module instead")]
 #[cfg(feature = "with-deprecated")]
 pub use future::{done, empty, failed, finished, lazy};
@@ -200,6 +203,7 @@ if_std! {
     pub mod task;
     pub mod executor;
     pub mod sync;
+    pub mod timer;
 
     #[doc(hidden)]
     #[deprecated(since = "0.1.4", note = "use sync::oneshot::channel instead")]
@@ -0,0 +1,38 @@
+use core::fmt;
+
+/// A type with no values, used as the error type of futures and streams
+/// which can never fail.
+///
+/// As a `Never` can never be constructed, a `Result<T, Never>` is always
+/// `Ok`. A value of this type can be turned into any other type with an empty
+/// `match`:
+///
+/// ```
+/// use futures::Never;
+///
+/// fn absurd<T>(never: Never) -> T {
+///     match never {}
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Never {}
+
+impl fmt::Debug for Never {
+    fn fmt(&self, _fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {}
+    }
+}
+
+impl fmt::Display for Never {
+    fn fmt(&self, _fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {}
+    }
+}
+
+if_std! {
+    impl ::std::error::Error for Never {
+        fn description(&self) -> &str {
+            match *self {}
+        }
+    }
+}
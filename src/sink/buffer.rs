@@ -79,4 +79,10 @@ impl<S: Sink> Sink for Buffer<S> {
         debug_assert!(self.buf.is_empty());
         self.sink.poll_complete()
     }
+
+    fn close(&mut self) -> Poll<(), Self::SinkError> {
+        try_ready!(self.try_empty_buffer());
+        debug_assert!(self.buf.is_empty());
+        self.sink.close()
+    }
 }
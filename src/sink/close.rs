@@ -0,0 +1,45 @@
+use {Poll, Async, Future};
+use sink::Sink;
+
+/// Future for the `close` function, which polls the sink until it has been
+/// closed.
+#[must_use = "futures do nothing unless polled"]
+pub struct Close<S> {
+    sink: Option<S>,
+}
+
+/// Creates a future which closes the given sink, flushing any pending
+/// requests first.
+///
+/// The sink itself is returned once it has been closed, although no further
+/// values should be sent to it.
+pub fn close<S: Sink>(sink: S) -> Close<S> {
+    Close { sink: Some(sink) }
+}
+
+impl<S: Sink> Close<S> {
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &S {
+        self.sink.as_ref().expect("Attempted `Close::get_ref` after the close completed")
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.sink.as_mut().expect("Attempted `Close::get_mut` after the close completed")
+    }
+}
+
+impl<S: Sink> Future for Close<S> {
+    type Item = S;
+    type Error = S::SinkError;
+
+    fn poll(&mut self) -> Poll<S, S::SinkError> {
+        let mut sink = self.sink.take().expect("Attempted to poll Close after it completed");
+        if try!(sink.close()).is_ready() {
+            Ok(Async::Ready(sink))
+        } else {
+            self.sink = Some(sink);
+            Ok(Async::NotReady)
+        }
+    }
+}
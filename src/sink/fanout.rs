@@ -0,0 +1,128 @@
+use {Poll, Async, StartSend, AsyncSink};
+use sink::Sink;
+
+/// Sink for the `Sink::fanout` combinator, which sends each value to two
+/// underlying sinks.
+#[must_use = "sinks do nothing unless polled"]
+pub struct Fanout<A: Sink, B: Sink> {
+    left: Downstream<A>,
+    right: Downstream<B>,
+}
+
+pub fn new<A: Sink, B: Sink>(a: A, b: B) -> Fanout<A, B> {
+    Fanout {
+        left: Downstream::new(a),
+        right: Downstream::new(b),
+    }
+}
+
+impl<A: Sink, B: Sink> Fanout<A, B> {
+    /// Get shared references to the inner sinks.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.left.sink, &self.right.sink)
+    }
+
+    /// Get mutable references to the inner sinks.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.left.sink, &mut self.right.sink)
+    }
+
+    /// Consumes this combinator, returning the underlying sinks.
+    ///
+    /// Note that this may discard an item which has been accepted by this
+    /// sink, but not yet by one of the underlying sinks.
+    pub fn into_inner(self) -> (A, B) {
+        (self.left.sink, self.right.sink)
+    }
+}
+
+impl<A, B> Sink for Fanout<A, B>
+    where A: Sink,
+          A::SinkItem: Clone,
+          B: Sink<SinkItem = A::SinkItem, SinkError = A::SinkError>,
+{
+    type SinkItem = A::SinkItem;
+    type SinkError = A::SinkError;
+
+    fn start_send(&mut self, item: Self::SinkItem)
+                  -> StartSend<Self::SinkItem, Self::SinkError>
+    {
+        // Both sinks need to have accepted the previous item before we can
+        // take the next one.
+        let left_ready = try!(self.left.keep_flushing()).is_ready();
+        let right_ready = try!(self.right.keep_flushing()).is_ready();
+        if !left_ready || !right_ready {
+            return Ok(AsyncSink::NotReady(item))
+        }
+
+        try!(self.left.send(item.clone()));
+        try!(self.right.send(item));
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
+        let left_ready = try!(self.left.poll_complete()).is_ready();
+        let right_ready = try!(self.right.poll_complete()).is_ready();
+        if left_ready && right_ready {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+
+    fn close(&mut self) -> Poll<(), Self::SinkError> {
+        let left_ready = try!(self.left.close()).is_ready();
+        let right_ready = try!(self.right.close()).is_ready();
+        if left_ready && right_ready {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+// One of the sinks of a `Fanout`, along with an item it hasn't accepted yet.
+struct Downstream<S: Sink> {
+    sink: S,
+    pending: Option<S::SinkItem>,
+}
+
+impl<S: Sink> Downstream<S> {
+    fn new(sink: S) -> Downstream<S> {
+        Downstream {
+            sink: sink,
+            pending: None,
+        }
+    }
+
+    fn send(&mut self, item: S::SinkItem) -> Result<(), S::SinkError> {
+        debug_assert!(self.pending.is_none());
+        if let AsyncSink::NotReady(item) = try!(self.sink.start_send(item)) {
+            self.pending = Some(item);
+        }
+        Ok(())
+    }
+
+    fn keep_flushing(&mut self) -> Poll<(), S::SinkError> {
+        if let Some(item) = self.pending.take() {
+            if let AsyncSink::NotReady(item) = try!(self.sink.start_send(item)) {
+                self.pending = Some(item);
+                return Ok(Async::NotReady)
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        // Always propagate `poll_complete` downward, even if an item is still
+        // pending, to make progress on the items which were accepted.
+        let me_ready = try!(self.keep_flushing());
+        try_ready!(self.sink.poll_complete());
+        Ok(me_ready)
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        try_ready!(self.keep_flushing());
+        self.sink.close()
+    }
+}
@@ -0,0 +1,68 @@
+use {Poll, StartSend};
+use sink::Sink;
+use stream::Stream;
+
+/// Sink for the `Sink::sink_map_err` combinator.
+#[must_use = "sinks do nothing unless polled"]
+pub struct SinkMapErr<S, F> {
+    sink: S,
+    f: Option<F>,
+}
+
+pub fn new<S, F>(sink: S, f: F) -> SinkMapErr<S, F> {
+    SinkMapErr {
+        sink: sink,
+        f: Some(f),
+    }
+}
+
+impl<S, F> SinkMapErr<S, F> {
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    fn map_err<E, U>(&mut self, e: E) -> U
+        where F: FnOnce(E) -> U
+    {
+        let f = self.f.take().expect("cannot use SinkMapErr after an error");
+        f(e)
+    }
+}
+
+// Forwarding impl of Stream from the underlying sink
+impl<S, F> Stream for SinkMapErr<S, F>
+    where S: Stream
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.sink.poll()
+    }
+}
+
+impl<S, F, E> Sink for SinkMapErr<S, F>
+    where S: Sink,
+          F: FnOnce(S::SinkError) -> E,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = E;
+
+    fn start_send(&mut self, item: S::SinkItem) -> StartSend<S::SinkItem, E> {
+        self.sink.start_send(item).map_err(|e| self.map_err(e))
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), E> {
+        self.sink.poll_complete().map_err(|e| self.map_err(e))
+    }
+
+    fn close(&mut self) -> Poll<(), E> {
+        self.sink.close().map_err(|e| self.map_err(e))
+    }
+}
@@ -7,9 +7,13 @@ use {IntoFuture, Poll, StartSend};
 use stream::Stream;
 
 mod with;
-// mod with_map;
-// mod with_filter;
-// mod with_filter_map;
+mod with_map;
+mod with_filter;
+mod with_filter_map;
+mod with_flat_map;
+mod map_err;
+mod close;
+mod fanout;
 mod flush;
 mod send;
 mod send_all;
@@ -34,6 +38,10 @@ if_std! {
         fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
             Ok(::Async::Ready(()))
         }
+
+        fn close(&mut self) -> Poll<(), Self::SinkError> {
+            Ok(::Async::Ready(()))
+        }
     }
 
     /// A type alias for `Box<Stream + Send>`
@@ -52,10 +60,21 @@ if_std! {
         fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
             (**self).poll_complete()
         }
+
+        fn close(&mut self) -> Poll<(), Self::SinkError> {
+            (**self).close()
+        }
     }
 }
 
 pub use self::with::With;
+pub use self::with_map::WithMap;
+pub use self::with_filter::WithFilter;
+pub use self::with_filter_map::WithFilterMap;
+pub use self::with_flat_map::WithFlatMap;
+pub use self::map_err::SinkMapErr;
+pub use self::close::{close, Close};
+pub use self::fanout::Fanout;
 pub use self::flush::Flush;
 pub use self::send::Send;
 pub use self::send_all::SendAll;
@@ -170,6 +189,28 @@ pub trait Sink {
     /// error.
     fn poll_complete(&mut self) -> Poll<(), Self::SinkError>;
 
+    /// A method to indicate that no more values will ever be pushed into this
+    /// sink, and to drive the sink to completion.
+    ///
+    /// This gives the sink a chance to perform any graceful shutdown, such as
+    /// sending a trailer or closing a connection, after all pending requests
+    /// have been processed. Like `poll_complete`, this returns
+    /// `Ok(Async::Ready(()))` once the sink has been closed, and schedules the
+    /// current task to be woken up otherwise. The `close` function returns a
+    /// future which drives this method to completion.
+    ///
+    /// The default implementation simply delegates to `poll_complete`, which
+    /// is appropriate for sinks with no notion of shutdown.
+    ///
+    /// # Panics
+    ///
+    /// This method may panic in the same situations as `poll_complete`.
+    /// Calling `start_send` after `close` has completed may also panic or
+    /// return an error, depending on the specific sink.
+    fn close(&mut self) -> Poll<(), Self::SinkError> {
+        self.poll_complete()
+    }
+
     /// Composes a function *in front of* the sink.
     ///
     /// This adapter produces a new sink that passes each value through the
@@ -190,19 +231,101 @@ pub trait Sink {
         with::new(self, f)
     }
 
-    /*
+    /// Composes a synchronous function *in front of* the sink.
+    ///
+    /// This adapter produces a new sink that passes each value through the
+    /// given function `f` before sending it to `self`. Unlike `with`, no
+    /// future is created for each value.
+    ///
+    /// Note that this function consumes the given sink, returning a wrapped
+    /// version, much like `Iterator::map`.
     fn with_map<U, F>(self, f: F) -> WithMap<Self, U, F>
         where F: FnMut(U) -> Self::SinkItem,
-              Self: Sized;
+              Self: Sized
+    {
+        with_map::new(self, f)
+    }
 
+    /// Filters the values sent to the sink with a predicate.
+    ///
+    /// This adapter produces a new sink that only sends the values for which
+    /// `f` returns `true` to `self`. All other values are accepted and then
+    /// silently dropped.
+    ///
+    /// Note that this function consumes the given sink, returning a wrapped
+    /// version, much like `Iterator::filter`.
     fn with_filter<F>(self, f: F) -> WithFilter<Self, F>
-        where F: FnMut(Self::SinkItem) -> bool,
-              Self: Sized;
+        where F: FnMut(&Self::SinkItem) -> bool,
+              Self: Sized
+    {
+        with_filter::new(self, f)
+    }
 
+    /// Composes a function *in front of* the sink which both filters and maps
+    /// values.
+    ///
+    /// This adapter produces a new sink that passes each value through the
+    /// given function `f`. If `f` returns `Some`, the contained value is sent
+    /// to `self`, and if it returns `None` the value is dropped.
+    ///
+    /// Note that this function consumes the given sink, returning a wrapped
+    /// version, much like `Iterator::filter_map`.
     fn with_filter_map<U, F>(self, f: F) -> WithFilterMap<Self, U, F>
         where F: FnMut(U) -> Option<Self::SinkItem>,
-              Self: Sized;
-     */
+              Self: Sized
+    {
+        with_filter_map::new(self, f)
+    }
+
+    /// Composes a function *in front of* the sink which expands each value
+    /// into a stream of values.
+    ///
+    /// This adapter produces a new sink that passes each value through the
+    /// given function `f`, and then sends every item of the resulting stream
+    /// to `self`. The next value isn't accepted until the stream for the
+    /// previous one has been exhausted. If the stream produces an error, that
+    /// error is returned by the new sink.
+    ///
+    /// Note that this function consumes the given sink, returning a wrapped
+    /// version, much like `Iterator::flat_map`.
+    fn with_flat_map<U, F, St>(self, f: F) -> WithFlatMap<Self, U, F, St>
+        where F: FnMut(U) -> St,
+              St: Stream<Item = Self::SinkItem, Error = Self::SinkError>,
+              Self: Sized
+    {
+        with_flat_map::new(self, f)
+    }
+
+    /// Transforms the error returned by the sink.
+    ///
+    /// The closure `f` is called on the first error returned by the sink,
+    /// converting it to a new type. Sinks aren't expected to be used after
+    /// returning an error, and using the returned sink after it has returned
+    /// an error will panic.
+    fn sink_map_err<F, E>(self, f: F) -> SinkMapErr<Self, F>
+        where F: FnOnce(Self::SinkError) -> E,
+              Self: Sized
+    {
+        map_err::new(self, f)
+    }
+
+    /// Fans out items to multiple sinks.
+    ///
+    /// This adapter produces a new sink that clones each value it's sent into
+    /// both `self` and `other`. A value is only accepted once both sinks have
+    /// accepted the previous one, so if only one of them is ready the value
+    /// is held on to until the other one catches up. `poll_complete` and
+    /// `close` are propagated to both sinks.
+    ///
+    /// Note that this function consumes the given sinks, returning a wrapped
+    /// version.
+    fn fanout<S>(self, other: S) -> Fanout<Self, S>
+        where S: Sink<SinkItem = Self::SinkItem, SinkError = Self::SinkError>,
+              Self::SinkItem: Clone,
+              Self: Sized
+    {
+        fanout::new(self, other)
+    }
 
     /// Adds a fixed-size buffer to the current sink.
     ///
@@ -276,4 +399,8 @@ impl<'a, S: ?Sized + Sink> Sink for &'a mut S {
     fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
         (**self).poll_complete()
     }
+
+    fn close(&mut self) -> Poll<(), Self::SinkError> {
+        (**self).close()
+    }
 }
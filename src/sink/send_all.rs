@@ -76,11 +76,11 @@ impl<T, U> Future for SendAll<T, U>
             if let Some(item) = try_ready!(self.stream_mut().poll()) {
                 try_ready!(self.try_start_send(item))
             } else {
-                // we're done pushing the stream, but want to block on flushing the
-                // sink
-                try_ready!(self.sink_mut().poll_complete());
+                // we're done pushing the stream, but want to block on flushing
+                // and closing the sink
+                try_ready!(self.sink_mut().close());
 
-                // now everything's emptied, so return the sink for further use
+                // now everything's emptied, so return the sink
                 return Ok(Async::Ready(self.take_result()))
             }
         }
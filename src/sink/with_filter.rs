@@ -0,0 +1,94 @@
+use {Poll, Async, StartSend, AsyncSink};
+use sink::Sink;
+use stream::Stream;
+
+/// Sink for the `Sink::with_filter` combinator, which only pushes values
+/// matching a predicate into the underlying sink.
+#[must_use = "sinks do nothing unless polled"]
+pub struct WithFilter<S, F>
+    where S: Sink,
+{
+    sink: S,
+    f: F,
+    // An item which has already been accepted by the predicate, but was
+    // rejected by the underlying sink.
+    buffered: Option<S::SinkItem>,
+}
+
+pub fn new<S, F>(sink: S, f: F) -> WithFilter<S, F>
+    where S: Sink,
+          F: FnMut(&S::SinkItem) -> bool,
+{
+    WithFilter {
+        sink: sink,
+        f: f,
+        buffered: None,
+    }
+}
+
+// Forwarding impl of Stream from the underlying sink
+impl<S, F> Stream for WithFilter<S, F>
+    where S: Stream + Sink,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.sink.poll()
+    }
+}
+
+impl<S, F> WithFilter<S, F>
+    where S: Sink,
+{
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    fn try_empty_buffer(&mut self) -> Poll<(), S::SinkError> {
+        if let Some(item) = self.buffered.take() {
+            if let AsyncSink::NotReady(item) = try!(self.sink.start_send(item)) {
+                self.buffered = Some(item);
+                return Ok(Async::NotReady)
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<S, F> Sink for WithFilter<S, F>
+    where S: Sink,
+          F: FnMut(&S::SinkItem) -> bool,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> StartSend<S::SinkItem, S::SinkError> {
+        if try!(self.try_empty_buffer()).is_not_ready() {
+            return Ok(AsyncSink::NotReady(item))
+        }
+        if (self.f)(&item) {
+            if let AsyncSink::NotReady(item) = try!(self.sink.start_send(item)) {
+                self.buffered = Some(item);
+            }
+        }
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        let me_ready = try!(self.try_empty_buffer());
+        try_ready!(self.sink.poll_complete());
+        Ok(me_ready)
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        try_ready!(self.try_empty_buffer());
+        self.sink.close()
+    }
+}
@@ -0,0 +1,121 @@
+use core::marker::PhantomData;
+
+use {Poll, Async, StartSend, AsyncSink};
+use sink::Sink;
+use stream::Stream;
+
+/// Sink for the `Sink::with_flat_map` combinator, chaining a computation that
+/// returns a stream of values to run *prior* to pushing them into the
+/// underlying sink.
+#[must_use = "sinks do nothing unless polled"]
+pub struct WithFlatMap<S, U, F, St>
+    where S: Sink,
+          F: FnMut(U) -> St,
+          St: Stream<Item = S::SinkItem, Error = S::SinkError>,
+{
+    sink: S,
+    f: F,
+    // The stream of items currently being pushed into the sink.
+    stream: Option<St>,
+    // An item taken from `stream` which was rejected by the underlying sink.
+    buffered: Option<S::SinkItem>,
+    _phantom: PhantomData<fn(U)>,
+}
+
+pub fn new<S, U, F, St>(sink: S, f: F) -> WithFlatMap<S, U, F, St>
+    where S: Sink,
+          F: FnMut(U) -> St,
+          St: Stream<Item = S::SinkItem, Error = S::SinkError>,
+{
+    WithFlatMap {
+        sink: sink,
+        f: f,
+        stream: None,
+        buffered: None,
+        _phantom: PhantomData,
+    }
+}
+
+// Forwarding impl of Stream from the underlying sink
+impl<S, U, F, St> Stream for WithFlatMap<S, U, F, St>
+    where S: Stream + Sink,
+          F: FnMut(U) -> St,
+          St: Stream<Item = S::SinkItem, Error = S::SinkError>,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.sink.poll()
+    }
+}
+
+impl<S, U, F, St> WithFlatMap<S, U, F, St>
+    where S: Sink,
+          F: FnMut(U) -> St,
+          St: Stream<Item = S::SinkItem, Error = S::SinkError>,
+{
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    fn try_empty_stream(&mut self) -> Poll<(), S::SinkError> {
+        loop {
+            let item = match self.buffered.take() {
+                Some(item) => item,
+                None => {
+                    let item = match self.stream {
+                        Some(ref mut stream) => try_ready!(stream.poll()),
+                        None => return Ok(Async::Ready(())),
+                    };
+                    match item {
+                        Some(item) => item,
+                        None => {
+                            self.stream = None;
+                            return Ok(Async::Ready(()))
+                        }
+                    }
+                }
+            };
+            if let AsyncSink::NotReady(item) = try!(self.sink.start_send(item)) {
+                self.buffered = Some(item);
+                return Ok(Async::NotReady)
+            }
+        }
+    }
+}
+
+impl<S, U, F, St> Sink for WithFlatMap<S, U, F, St>
+    where S: Sink,
+          F: FnMut(U) -> St,
+          St: Stream<Item = S::SinkItem, Error = S::SinkError>,
+{
+    type SinkItem = U;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: U) -> StartSend<U, S::SinkError> {
+        if try!(self.try_empty_stream()).is_not_ready() {
+            return Ok(AsyncSink::NotReady(item))
+        }
+        self.stream = Some((self.f)(item));
+        try!(self.try_empty_stream());
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        let me_ready = try!(self.try_empty_stream());
+        try_ready!(self.sink.poll_complete());
+        Ok(me_ready)
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        try_ready!(self.try_empty_stream());
+        self.sink.close()
+    }
+}
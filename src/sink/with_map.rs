@@ -0,0 +1,99 @@
+use core::marker::PhantomData;
+
+use {Poll, Async, StartSend, AsyncSink};
+use sink::Sink;
+use stream::Stream;
+
+/// Sink for the `Sink::with_map` combinator, mapping each value with a
+/// function *prior* to pushing it into the underlying sink.
+#[must_use = "sinks do nothing unless polled"]
+pub struct WithMap<S, U, F>
+    where S: Sink,
+          F: FnMut(U) -> S::SinkItem,
+{
+    sink: S,
+    f: F,
+    // An item which has already been mapped, but was rejected by the
+    // underlying sink.
+    buffered: Option<S::SinkItem>,
+    _phantom: PhantomData<fn(U)>,
+}
+
+pub fn new<S, U, F>(sink: S, f: F) -> WithMap<S, U, F>
+    where S: Sink,
+          F: FnMut(U) -> S::SinkItem,
+{
+    WithMap {
+        sink: sink,
+        f: f,
+        buffered: None,
+        _phantom: PhantomData,
+    }
+}
+
+// Forwarding impl of Stream from the underlying sink
+impl<S, U, F> Stream for WithMap<S, U, F>
+    where S: Stream + Sink,
+          F: FnMut(U) -> S::SinkItem,
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        self.sink.poll()
+    }
+}
+
+impl<S, U, F> WithMap<S, U, F>
+    where S: Sink,
+          F: FnMut(U) -> S::SinkItem,
+{
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    fn try_empty_buffer(&mut self) -> Poll<(), S::SinkError> {
+        if let Some(item) = self.buffered.take() {
+            if let AsyncSink::NotReady(item) = try!(self.sink.start_send(item)) {
+                self.buffered = Some(item);
+                return Ok(Async::NotReady)
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<S, U, F> Sink for WithMap<S, U, F>
+    where S: Sink,
+          F: FnMut(U) -> S::SinkItem,
+{
+    type SinkItem = U;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: U) -> StartSend<U, S::SinkError> {
+        if try!(self.try_empty_buffer()).is_not_ready() {
+            return Ok(AsyncSink::NotReady(item))
+        }
+        if let AsyncSink::NotReady(item) = try!(self.sink.start_send((self.f)(item))) {
+            self.buffered = Some(item);
+        }
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        let me_ready = try!(self.try_empty_buffer());
+        try_ready!(self.sink.poll_complete());
+        Ok(me_ready)
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        try_ready!(self.try_empty_buffer());
+        self.sink.close()
+    }
+}
@@ -24,6 +24,10 @@ pub struct Drain<T> {
     head: *mut Node<T>,
 }
 
+// A `Drain` owns every node in its list, so it can be sent to another thread
+// whenever the items themselves can.
+unsafe impl<T: Send> Send for Drain<T> {}
+
 impl<T> Stack<T> {
     pub fn new() -> Stack<T> {
         Stack {
@@ -61,6 +65,12 @@ impl<T> Drop for Stack<T> {
     }
 }
 
+impl EventSet for Stack<usize> {
+    fn insert(&self, id: usize) {
+        self.push(id);
+    }
+}
+
 impl<T> Iterator for Drain<T> {
     type Item = T;
 
@@ -130,9 +140,3 @@ mod tests {
         assert_eq!(data.get(), 1);
     }
 }
-
-impl EventSet for Stack<usize> {
-    fn insert(&self, id: usize) {
-        self.push(id);
-    }
-}
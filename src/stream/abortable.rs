@@ -0,0 +1,25 @@
+use future::{Abortable, AbortHandle};
+use stream::Stream;
+
+/// Creates a new `Abortable` stream along with an `AbortHandle` which can be
+/// used to abort it.
+///
+/// Once `AbortHandle::abort` has been called, the stream will return
+/// `AbortError::Aborted` every time it's polled. Errors of the wrapped stream
+/// are returned as `AbortError::Inner`.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Stream;
+/// use futures::future::AbortError;
+/// use futures::stream;
+///
+/// let (stream, handle) = stream::abortable(stream::iter::<_, _, ()>(vec![Ok(1)]));
+/// handle.abort();
+/// assert_eq!(stream.wait().next(), Some(Err(AbortError::Aborted)));
+/// ```
+pub fn abortable<S: Stream>(stream: S) -> (Abortable<S>, AbortHandle) {
+    let (handle, reg) = AbortHandle::new_pair();
+    (Abortable::new(stream, reg), handle)
+}
@@ -1,12 +1,5 @@
-use std::prelude::v1::*;
-use std::mem;
-use std::sync::Arc;
-
-use task::{self, UnparkEvent};
-
-use {Async, IntoFuture, Poll, Future};
-use stream::{Stream, Fuse};
-use stack::{Stack, Drain};
+use {Async, IntoFuture, Poll};
+use stream::{Stream, Fuse, FuturesUnordered};
 
 /// An adaptor for a stream of futures to execute the futures concurrently, if
 /// possible, delivering results as they become available.
@@ -20,31 +13,8 @@ pub struct BufferUnordered<S>
           S::Item: IntoFuture,
 {
     stream: Fuse<S>,
-
-    // A slab of futures that are being executed. Each slot in this vector is
-    // either an active future or a pointer to the next empty slot. This is used
-    // to get O(1) deallocation in the slab and O(1) allocation.
-    //
-    // The `next_future` field is the next slot in the `futures` array that's a
-    // `Slot::Next` variant. If it points to the end of the array then the array
-    // is full.
-    futures: Vec<Slot<<S::Item as IntoFuture>::Future>>,
-    next_future: usize,
-
-    // A list of events that will get pushed onto concurrently by our many
-    // futures. This is filled in and used with the `with_unpark_event`
-    // function. The `pending` list here is the last time we drained events from
-    // our stack.
-    stack: Arc<Stack<usize>>,
-    pending: Drain<usize>,
-
-    // Number of active futures running in the `futures` slab
-    active: usize,
-}
-
-enum Slot<T> {
-    Next(usize),
-    Data(T),
+    queue: FuturesUnordered<<S::Item as IntoFuture>::Future>,
+    max: usize,
 }
 
 pub fn new<S>(s: S, amt: usize) -> BufferUnordered<S>
@@ -53,39 +23,8 @@ pub fn new<S>(s: S, amt: usize) -> BufferUnordered<S>
 {
     BufferUnordered {
         stream: super::fuse::new(s),
-        futures: (0..amt).map(|i| Slot::Next(i + 1)).collect(),
-        next_future: 0,
-        pending: Stack::new().drain(),
-        stack: Arc::new(Stack::new()),
-        active: 0,
-    }
-}
-
-impl<S> BufferUnordered<S>
-    where S: Stream,
-          S::Item: IntoFuture<Error=<S as Stream>::Error>,
-{
-    fn poll_pending(&mut self)
-                    -> Option<Poll<Option<<S::Item as IntoFuture>::Item>,
-                                   S::Error>> {
-        while let Some(idx) = self.pending.next() {
-            let result = match self.futures[idx] {
-                Slot::Data(ref mut f) => {
-                    let event = UnparkEvent::new(self.stack.clone(), idx);
-                    match task::with_unpark_event(event, || f.poll()) {
-                        Ok(Async::NotReady) => continue,
-                        Ok(Async::Ready(e)) => Ok(Async::Ready(Some(e))),
-                        Err(e) => Err(e),
-                    }
-                },
-                Slot::Next(_) => continue,
-            };
-            self.active -= 1;
-            self.futures[idx] = Slot::Next(self.next_future);
-            self.next_future = idx;
-            return Some(result)
-        }
-        None
+        queue: FuturesUnordered::new(),
+        max: amt,
     }
 }
 
@@ -98,44 +37,30 @@ impl<S> Stream for BufferUnordered<S>
 
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
         // First up, try to spawn off as many futures as possible by filling up
-        // our slab of futures.
-        while self.next_future < self.futures.len() {
+        // our set of futures.
+        while self.queue.len() < self.max {
             let future = match try!(self.stream.poll()) {
                 Async::Ready(Some(s)) => s.into_future(),
                 Async::Ready(None) |
                 Async::NotReady => break,
             };
-            self.active += 1;
-            self.stack.push(self.next_future);
-            match mem::replace(&mut self.futures[self.next_future],
-                               Slot::Data(future)) {
-                Slot::Next(next) => self.next_future = next,
-                Slot::Data(_) => panic!(),
-            }
-        }
 
-        // Next, see if our list of `pending` events from last time has any
-        // items, and if so process them here.
-        if let Some(ret) = self.poll_pending() {
-            return ret
+            self.queue.push(future);
         }
 
-        // And finally, take a look at our stack of events, attempting to
-        // process all of those.
-        assert!(self.pending.next().is_none());
-        self.pending = self.stack.drain();
-        if let Some(ret) = self.poll_pending() {
-            return ret
+        // Try polling a new future
+        if let Some(val) = try_ready!(self.queue.poll()) {
+            return Ok(Async::Ready(Some(val)));
         }
 
         // If we've gotten this far then there's no events for us to process and
         // nothing was ready, so figure out if we're not done yet or if we've
         // reached the end.
-        Ok(if self.active > 0 || !self.stream.is_done() {
-            Async::NotReady
+        if self.stream.is_done() {
+            Ok(Async::Ready(None))
         } else {
-            Async::Ready(None)
-        })
+            Ok(Async::NotReady)
+        }
     }
 }
 
@@ -154,4 +79,8 @@ impl<S> ::sink::Sink for BufferUnordered<S>
     fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
         self.stream.poll_complete()
     }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
 }
@@ -35,6 +35,10 @@ impl<S, F> ::sink::Sink for FilterMap<S, F>
     fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
         self.stream.poll_complete()
     }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
 }
 
 impl<S, F, B> Stream for FilterMap<S, F>
@@ -0,0 +1,191 @@
+use std::prelude::v1::*;
+use std::iter::FromIterator;
+use std::mem;
+use std::sync::Arc;
+
+use task::{self, UnparkEvent};
+
+use {Async, Future, IntoFuture, Poll};
+use stream::Stream;
+use stack::{Stack, Drain};
+
+/// A set of `Future`s which may complete in any order.
+///
+/// This structure is optimized to manage a large number of futures. Futures
+/// managed by `FuturesUnordered` will only be polled when they generate
+/// notifications. This reduces the required amount of work needed to poll
+/// large numbers of futures.
+///
+/// `FuturesUnordered` can be filled by `collect`ing an iterator of `Future`s
+/// into a `FuturesUnordered`, or by `push`ing `Future`s onto an existing
+/// `FuturesUnordered`. When new `Future`s are added, `poll` must be called in
+/// order to begin receiving wakeups for new `Future`s.
+///
+/// Note that you can create a ready-made `FuturesUnordered` via the
+/// `futures_unordered` function in the `stream` module, or you can start with
+/// an empty set with the `FuturesUnordered::new` constructor.
+#[must_use = "streams do nothing unless polled"]
+pub struct FuturesUnordered<F> {
+    // A slab of futures that are being executed. Each slot in this vector is
+    // either an active future or a pointer to the next empty slot. This is used
+    // to get O(1) deallocation in the slab and O(1) allocation.
+    //
+    // The `next_future` field is the next slot in the `futures` array that's a
+    // `Slot::Next` variant. If it points to the end of the array then the array
+    // is full, and the next future pushed will grow it.
+    futures: Vec<Slot<F>>,
+    next_future: usize,
+
+    // A list of events that will get pushed onto concurrently by our many
+    // futures. This is filled in and used with the `with_unpark_event`
+    // function. The `pending` list here is the last time we drained events from
+    // our stack.
+    stack: Arc<Stack<usize>>,
+    pending: Drain<usize>,
+
+    // Number of active futures running in the `futures` slab
+    active: usize,
+}
+
+enum Slot<T> {
+    Next(usize),
+    Data(T),
+}
+
+/// Converts a list of futures into a `Stream` of results from the futures.
+///
+/// This function will take an list of futures (e.g. a vector, an iterator,
+/// etc), and return a stream. The stream will yield items as they become
+/// available on the futures internally, in the order that they become
+/// available. This function is similar to `buffer_unordered` in that it may
+/// return items in a different order than in the list specified.
+///
+/// Note that the returned set can also be used to dynamically push more
+/// futures into the set as they become available.
+pub fn futures_unordered<I>(futures: I) -> FuturesUnordered<<I::Item as IntoFuture>::Future>
+    where I: IntoIterator,
+          I::Item: IntoFuture,
+{
+    futures.into_iter().map(|f| f.into_future()).collect()
+}
+
+impl<F> FuturesUnordered<F> where F: Future {
+    /// Constructs a new, empty `FuturesUnordered`
+    ///
+    /// The returned `FuturesUnordered` does not contain any futures and, in
+    /// this state, `FuturesUnordered::poll` will return `Ok(Async::Ready(None))`.
+    pub fn new() -> FuturesUnordered<F> {
+        FuturesUnordered {
+            futures: Vec::new(),
+            next_future: 0,
+            stack: Arc::new(Stack::new()),
+            pending: Stack::new().drain(),
+            active: 0,
+        }
+    }
+
+    /// Returns the number of futures contained in the set.
+    ///
+    /// This represents the total number of in-flight futures.
+    pub fn len(&self) -> usize {
+        self.active
+    }
+
+    /// Returns `true` if the set contains no futures
+    pub fn is_empty(&self) -> bool {
+        self.active == 0
+    }
+
+    /// Push a future into the set.
+    ///
+    /// This function submits the given future to the set for managing. This
+    /// function will not call `poll` on the submitted future. The caller must
+    /// ensure that `FuturesUnordered::poll` is called in order to receive task
+    /// notifications.
+    pub fn push(&mut self, future: F) {
+        let idx = self.next_future;
+        if idx == self.futures.len() {
+            self.futures.push(Slot::Data(future));
+            self.next_future += 1;
+        } else {
+            match mem::replace(&mut self.futures[idx], Slot::Data(future)) {
+                Slot::Next(next) => self.next_future = next,
+                Slot::Data(_) => panic!(),
+            }
+        }
+        self.active += 1;
+
+        // Schedule the new future to get polled the next time we are.
+        self.stack.push(idx);
+    }
+
+    fn poll_pending(&mut self) -> Option<Poll<Option<F::Item>, F::Error>> {
+        for idx in self.pending.by_ref() {
+            let result = match self.futures[idx] {
+                Slot::Data(ref mut f) => {
+                    let event = UnparkEvent::new(self.stack.clone(), idx);
+                    match task::with_unpark_event(event, || f.poll()) {
+                        Ok(Async::NotReady) => continue,
+                        Ok(Async::Ready(e)) => Ok(Async::Ready(Some(e))),
+                        Err(e) => Err(e),
+                    }
+                },
+                Slot::Next(_) => continue,
+            };
+            self.active -= 1;
+            self.futures[idx] = Slot::Next(self.next_future);
+            self.next_future = idx;
+            return Some(result)
+        }
+        None
+    }
+}
+
+impl<F> Stream for FuturesUnordered<F>
+    where F: Future
+{
+    type Item = F::Item;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Poll<Option<F::Item>, F::Error> {
+        if self.active == 0 {
+            return Ok(Async::Ready(None))
+        }
+
+        // First, see if our list of `pending` events from last time has any
+        // items, and if so process them here.
+        if let Some(ret) = self.poll_pending() {
+            return ret
+        }
+
+        // And finally, take a look at our stack of events, attempting to
+        // process all of those.
+        assert!(self.pending.next().is_none());
+        self.pending = self.stack.drain();
+        if let Some(ret) = self.poll_pending() {
+            return ret
+        }
+
+        // No futures were notified, but we've still got futures in flight, so
+        // we'll hear about them later.
+        Ok(Async::NotReady)
+    }
+}
+
+impl<F: Future> Default for FuturesUnordered<F> {
+    fn default() -> FuturesUnordered<F> {
+        FuturesUnordered::new()
+    }
+}
+
+impl<F: Future> FromIterator<F> for FuturesUnordered<F> {
+    fn from_iter<T>(iter: T) -> Self
+        where T: IntoIterator<Item = F>
+    {
+        let mut new = FuturesUnordered::new();
+        for future in iter {
+            new.push(future);
+        }
+        new
+    }
+}
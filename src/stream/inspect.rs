@@ -0,0 +1,59 @@
+use {Async, Poll};
+use stream::Stream;
+
+/// Do something with the items of a stream, passing them on.
+///
+/// This is produced by the `Stream::inspect` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct Inspect<S, F> where S: Stream {
+    stream: S,
+    inspect: F,
+}
+
+pub fn new<S, F>(stream: S, f: F) -> Inspect<S, F>
+    where S: Stream,
+          F: FnMut(&S::Item),
+{
+    Inspect {
+        stream: stream,
+        inspect: f,
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, F> ::sink::Sink for Inspect<S, F>
+    where S: ::sink::Sink + Stream
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, F> Stream for Inspect<S, F>
+    where S: Stream,
+          F: FnMut(&S::Item),
+{
+    type Item = S::Item;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, S::Error> {
+        match try_ready!(self.stream.poll()) {
+            Some(e) => {
+                (self.inspect)(&e);
+                Ok(Async::Ready(Some(e)))
+            }
+            None => Ok(Async::Ready(None)),
+        }
+    }
+}
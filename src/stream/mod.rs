@@ -11,12 +11,13 @@
 //! ready as well.
 // TODO: expand these docs
 
-use {IntoFuture, Poll};
+use {IntoFuture, Never, Poll};
 
 mod iter;
 pub use self::iter::{iter, IterStream};
 
 mod and_then;
+mod chain;
 mod empty;
 mod filter;
 mod filter_map;
@@ -25,21 +26,26 @@ mod fold;
 mod for_each;
 mod fuse;
 mod future;
+mod inspect;
 mod map;
 mod map_err;
 mod merge;
 mod once;
 mod or_else;
 mod peek;
+mod results;
+mod scan;
 mod select;
 mod skip;
 mod skip_while;
 mod take;
+mod take_while;
 mod then;
 mod unfold;
 mod zip;
 mod forward;
 pub use self::and_then::AndThen;
+pub use self::chain::Chain;
 pub use self::empty::{Empty, empty};
 pub use self::filter::Filter;
 pub use self::filter_map::FilterMap;
@@ -48,16 +54,20 @@ pub use self::fold::Fold;
 pub use self::for_each::ForEach;
 pub use self::fuse::Fuse;
 pub use self::future::StreamFuture;
+pub use self::inspect::Inspect;
 pub use self::map::Map;
 pub use self::map_err::MapErr;
 pub use self::merge::{Merge, MergedItem};
 pub use self::once::{Once, once};
 pub use self::or_else::OrElse;
 pub use self::peek::Peekable;
+pub use self::results::{Results, FlattenResults};
+pub use self::scan::Scan;
 pub use self::select::Select;
 pub use self::skip::Skip;
 pub use self::skip_while::SkipWhile;
 pub use self::take::Take;
+pub use self::take_while::TakeWhile;
 pub use self::then::Then;
 pub use self::unfold::{Unfold, unfold};
 pub use self::zip::Zip;
@@ -67,21 +77,25 @@ use sink::{Sink};
 if_std! {
     use std;
 
+    mod abortable;
     mod buffered;
     mod buffer_unordered;
     mod catch_unwind;
     mod chunks;
     mod collect;
+    mod futures_unordered;
     mod wait;
     mod channel;
     mod split;
+    pub use self::abortable::abortable;
     pub use self::buffered::Buffered;
     pub use self::buffer_unordered::BufferUnordered;
     pub use self::catch_unwind::CatchUnwind;
     pub use self::chunks::Chunks;
     pub use self::collect::Collect;
+    pub use self::futures_unordered::{futures_unordered, FuturesUnordered};
     pub use self::wait::Wait;
-    pub use self::split::{SplitStream, SplitSink};
+    pub use self::split::{SplitStream, SplitSink, ReuniteError};
 
     #[doc(hidden)]
     #[cfg(feature = "with-deprecated")]
@@ -280,6 +294,32 @@ pub trait Stream {
         map_err::new(self, f)
     }
 
+    /// Do something with each item of this stream, afterwards passing it on.
+    ///
+    /// This is similar to the `Iterator::inspect` method in the standard
+    /// library where it allows easily inspecting each value as it passes
+    /// through the stream, for example to debug what's going on. Errors are
+    /// passed through without being inspected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{self, Stream};
+    /// use futures::Future;
+    ///
+    /// let mut seen = Vec::new();
+    /// let stream = stream::iter::<_, _, ()>((1..4).map(Ok));
+    /// let doubled = stream.inspect(|x| seen.push(*x)).map(|x| x * 2);
+    /// assert_eq!(doubled.collect().wait(), Ok(vec![2, 4, 6]));
+    /// assert_eq!(seen, vec![1, 2, 3]);
+    /// ```
+    fn inspect<F>(self, f: F) -> Inspect<Self, F>
+        where F: FnMut(&Self::Item),
+              Self: Sized
+    {
+        inspect::new(self, f)
+    }
+
     /// Filters the values produced by this stream according to the provided
     /// predicate.
     ///
@@ -524,6 +564,39 @@ pub trait Stream {
         fold::new(self, f, init)
     }
 
+    /// Combinator similar to `fold` that holds internal state and produces a
+    /// new stream.
+    ///
+    /// Accepts an initial state and a closure which is given a mutable
+    /// reference to that state along with each item of this stream. The
+    /// closure returns a future which resolves to an `Option`: `Some` yields
+    /// the contained value from the returned stream, and `None` ends the
+    /// stream, like `Iterator::scan` in the standard library.
+    ///
+    /// Errors from this stream or from the closure's future are passed
+    /// through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{self, Stream};
+    /// use futures::future::{ok, Future};
+    ///
+    /// let stream = stream::iter::<_, _, ()>((1..10).map(Ok));
+    /// let sums = stream.scan(0, |sum, x| {
+    ///     *sum += x;
+    ///     ok(if *sum < 10 { Some(*sum) } else { None })
+    /// });
+    /// assert_eq!(sums.collect().wait(), Ok(vec![1, 3, 6]));
+    /// ```
+    fn scan<T, F, R, B>(self, initial_state: T, f: F) -> Scan<Self, T, F, R>
+        where F: FnMut(&mut T, Self::Item) -> R,
+              R: IntoFuture<Item=Option<B>, Error=Self::Error>,
+              Self: Sized
+    {
+        scan::new(self, initial_state, f)
+    }
+
     /// Flattens a stream of streams into just one continuous stream.
     ///
     /// If this stream's elements are themselves streams then this combinator
@@ -580,6 +653,60 @@ pub trait Stream {
         skip_while::new(self, pred)
     }
 
+    /// Take elements from this stream while the predicate provided resolves
+    /// to `true`.
+    ///
+    /// This function, like `Iterator::take_while`, will take elements from the
+    /// stream until the `predicate` resolves to `false`. Once one element
+    /// returns false it will always return that the stream is done.
+    fn take_while<P, R>(self, pred: P) -> TakeWhile<Self, P, R>
+        where P: FnMut(&Self::Item) -> R,
+              R: IntoFuture<Item=bool, Error=Self::Error>,
+              Self: Sized
+    {
+        take_while::new(self, pred)
+    }
+
+    /// Converts the errors of this stream into items, creating a stream which
+    /// never fails.
+    ///
+    /// Each item `t` of this stream is yielded as `Ok(t)`, and each error `e`
+    /// as `Err(e)`. As the returned stream can't fail, consumers such as
+    /// `fold`, `collect` and `forward` will keep going past errors until the
+    /// underlying stream is exhausted. The `flatten_results` method performs
+    /// the inverse conversion.
+    ///
+    /// The error type `E` of the returned stream is picked by the caller, as
+    /// it's never produced. `Never` works for most consumers, while
+    /// `forward` needs the error type of the sink it's forwarding into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{self, Stream};
+    /// use futures::{Future, Never};
+    ///
+    /// let stream = stream::iter(vec![Ok(1), Err("bad"), Ok(3)]);
+    /// let results = stream.results::<Never>().collect().wait().unwrap();
+    /// assert_eq!(results, vec![Ok(1), Err("bad"), Ok(3)]);
+    /// ```
+    fn results<E>(self) -> Results<Self, E>
+        where Self: Sized
+    {
+        results::new(self)
+    }
+
+    /// Converts a stream of `Result`s which never fails back into a stream of
+    /// items and errors.
+    ///
+    /// Each `Ok(t)` is yielded as the item `t`, and each `Err(e)` is returned
+    /// as the error `e`. This is the inverse of the `results` method.
+    fn flatten_results<T, E>(self) -> FlattenResults<Self>
+        where Self: Stream<Item = Result<T, E>, Error = Never> + Sized
+    {
+        results::flatten(self)
+    }
+
     /// Runs this stream to completion, executing the provided closure for each
     /// element on the stream.
     ///
@@ -744,6 +871,28 @@ pub trait Stream {
         zip::new(self, other)
     }
 
+    /// Adapter for chaining two streams.
+    ///
+    /// The resulting stream emits elements from the first stream, and when
+    /// the first stream reaches the end, emits the elements from the second
+    /// stream. Errors from either stream are passed through as they occur.
+    ///
+    /// ```rust
+    /// use futures::stream::{self, Stream};
+    /// use futures::Future;
+    ///
+    /// let stream1 = stream::iter::<_, _, ()>(vec![Ok(10), Ok(20)]);
+    /// let stream2 = stream::iter(vec![Ok(30), Ok(40)]);
+    /// let stream = stream1.chain(stream2);
+    /// assert_eq!(stream.collect().wait(), Ok(vec![10, 20, 30, 40]));
+    /// ```
+    fn chain<S>(self, other: S) -> Chain<Self, S>
+        where S: Stream<Item = Self::Item, Error = Self::Error>,
+              Self: Sized
+    {
+        chain::new(self, other)
+    }
+
     /// Creates a new stream which exposes a `peek` method.
     ///
     /// Calling `peek` returns a reference to the next item in the stream.
@@ -822,6 +971,25 @@ pub trait Stream {
     {
         split::split(self)
     }
+
+    /// Yield a `TimeoutError::Elapsed` error whenever this stream goes
+    /// `duration` without producing an item.
+    ///
+    /// The timeout starts when the returned stream is created and restarts
+    /// every time this stream yields an item or an error. When it elapses
+    /// the returned stream yields `TimeoutError::Elapsed` and the timeout
+    /// starts over, so the stream can continue to be polled if idling is
+    /// acceptable. Errors of this stream are wrapped in
+    /// `TimeoutError::Inner`.
+    ///
+    /// The timeout is tracked by the default timer, see the `timer` module.
+    /// Use `Timer::timeout_stream` to use a different timer instead.
+    #[cfg(feature = "use_std")]
+    fn timeout(self, duration: ::std::time::Duration) -> ::timer::TimeoutStream<Self>
+        where Self: Sized
+    {
+        ::timer::timeout_stream(self, duration)
+    }
 }
 
 impl<'a, S: ?Sized + Stream> Stream for &'a mut S {
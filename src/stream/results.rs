@@ -0,0 +1,107 @@
+use core::marker::PhantomData;
+
+use {Async, Never, Poll};
+use stream::Stream;
+
+/// A stream combinator which turns the errors of a stream into items.
+///
+/// This is produced by the `Stream::results` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct Results<S, E> {
+    stream: S,
+    _phantom: PhantomData<fn() -> E>,
+}
+
+pub fn new<S: Stream, E>(s: S) -> Results<S, E> {
+    Results {
+        stream: s,
+        _phantom: PhantomData,
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, E> ::sink::Sink for Results<S, E>
+    where S: ::sink::Sink
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S: Stream, E> Stream for Results<S, E> {
+    type Item = Result<S::Item, S::Error>;
+    type Error = E;
+
+    fn poll(&mut self) -> Poll<Option<Result<S::Item, S::Error>>, E> {
+        match self.stream.poll() {
+            Ok(Async::Ready(Some(e))) => Ok(Async::Ready(Some(Ok(e)))),
+            Ok(Async::Ready(None)) => Ok(Async::Ready(None)),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(e) => Ok(Async::Ready(Some(Err(e)))),
+        }
+    }
+}
+
+/// A stream combinator which turns a stream of `Result`s back into a stream
+/// of items and errors.
+///
+/// This is produced by the `Stream::flatten_results` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct FlattenResults<S> {
+    stream: S,
+}
+
+pub fn flatten<S: Stream>(s: S) -> FlattenResults<S> {
+    FlattenResults { stream: s }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S> ::sink::Sink for FlattenResults<S>
+    where S: ::sink::Sink
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, T, E> Stream for FlattenResults<S>
+    where S: Stream<Item = Result<T, E>, Error = Never>,
+{
+    type Item = T;
+    type Error = E;
+
+    fn poll(&mut self) -> Poll<Option<T>, E> {
+        let item = match self.stream.poll() {
+            Ok(Async::Ready(item)) => item,
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Err(never) => match never {},
+        };
+        match item {
+            Some(Ok(e)) => Ok(Async::Ready(Some(e))),
+            Some(Err(e)) => Err(e),
+            None => Ok(Async::Ready(None)),
+        }
+    }
+}
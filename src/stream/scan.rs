@@ -0,0 +1,86 @@
+use {Async, Poll, IntoFuture, Future};
+use stream::Stream;
+
+/// A stream combinator which maps elements of a stream while threading some
+/// state through, until the closure signals the end of the stream.
+///
+/// This structure is produced by the `Stream::scan` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct Scan<S, T, F, R> where R: IntoFuture {
+    stream: S,
+    state: T,
+    f: F,
+    pending: Option<R::Future>,
+    done: bool,
+}
+
+pub fn new<S, T, F, R, B>(s: S, initial_state: T, f: F) -> Scan<S, T, F, R>
+    where S: Stream,
+          F: FnMut(&mut T, S::Item) -> R,
+          R: IntoFuture<Item=Option<B>, Error=S::Error>,
+{
+    Scan {
+        stream: s,
+        state: initial_state,
+        f: f,
+        pending: None,
+        done: false,
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S, T, F, R> ::sink::Sink for Scan<S, T, F, R>
+    where S: ::sink::Sink, R: IntoFuture
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    fn start_send(&mut self, item: S::SinkItem) -> ::StartSend<S::SinkItem, S::SinkError> {
+        self.stream.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), S::SinkError> {
+        self.stream.close()
+    }
+}
+
+impl<S, T, F, R, B> Stream for Scan<S, T, F, R>
+    where S: Stream,
+          F: FnMut(&mut T, S::Item) -> R,
+          R: IntoFuture<Item=Option<B>, Error=S::Error>,
+{
+    type Item = B;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Option<B>, S::Error> {
+        if self.done {
+            return Ok(Async::Ready(None));
+        }
+
+        if self.pending.is_none() {
+            let item = match try_ready!(self.stream.poll()) {
+                Some(e) => e,
+                None => return Ok(Async::Ready(None)),
+            };
+            self.pending = Some((self.f)(&mut self.state, item).into_future());
+        }
+
+        assert!(self.pending.is_some());
+        let ret = match self.pending.as_mut().unwrap().poll() {
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Ok(Async::Ready(item)) => {
+                if item.is_none() {
+                    self.done = true;
+                }
+                Ok(Async::Ready(item))
+            }
+            Err(e) => Err(e),
+        };
+        self.pending = None;
+        ret
+    }
+}
@@ -0,0 +1,122 @@
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::{Acquire, Release, AcqRel};
+
+use task::{self, Task};
+
+/// A synchronization primitive for task notification.
+///
+/// `AtomicTask` will coordinate concurrent notifications with the consumer
+/// potentially "updating" the underlying task to notify. This is useful in
+/// scenarios where a computation completes in another thread and wants to
+/// notify the consumer, but the consumer is in the process of being migrated
+/// to a new logical task.
+///
+/// Consumers should call `register` before checking the result of a
+/// computation and producers should call `notify` after producing the
+/// computation (this differs from the usual `thread::park` pattern). It is
+/// also permitted for `notify` to be called **before** `register`. This
+/// results in a no-op. As long as the result is checked after `register`, no
+/// notification is ever lost.
+///
+/// A single `AtomicTask` is meant to have one consumer at a time. If
+/// `register` is called concurrently from several threads then the tasks
+/// losing the race are unparked immediately, so they'll be polled again
+/// rather than miss a notification.
+pub struct AtomicTask {
+    state: AtomicUsize,
+    task: UnsafeCell<Option<Task>>,
+}
+
+// The task slot is free, and no notification is in progress.
+const WAITING: usize = 0;
+
+// A task is being stored in the slot by `register`.
+const REGISTERING: usize = 0b01;
+
+// The task in the slot is being taken out by `notify`.
+const NOTIFYING: usize = 0b10;
+
+impl AtomicTask {
+    /// Create an `AtomicTask` initialized with no task.
+    pub fn new() -> AtomicTask {
+        AtomicTask {
+            state: AtomicUsize::new(WAITING),
+            task: UnsafeCell::new(None),
+        }
+    }
+
+    /// Registers the current task to be notified on calls to `notify`.
+    ///
+    /// This replaces any previously registered task. Like `task::park`, this
+    /// function will panic if it's not called from within the context of a
+    /// task.
+    pub fn register(&self) {
+        self.register_task(task::park());
+    }
+
+    /// Registers the provided task to be notified on calls to `notify`.
+    ///
+    /// This replaces any previously registered task.
+    pub fn register_task(&self, task: Task) {
+        match self.state.compare_exchange(WAITING, REGISTERING, Acquire, Acquire) {
+            Ok(_) => {
+                // We have exclusive access to the slot until we leave the
+                // `REGISTERING` state.
+                unsafe {
+                    *self.task.get() = Some(task);
+                }
+
+                // If a notification came in while we were storing the task
+                // then `notify` left it to us, so take the task back out and
+                // unpark it ourselves.
+                if let Err(actual) = self.state.compare_exchange(REGISTERING, WAITING,
+                                                                 AcqRel, Acquire) {
+                    debug_assert_eq!(actual, REGISTERING | NOTIFYING);
+                    let task = unsafe { (*self.task.get()).take().unwrap() };
+                    self.state.swap(WAITING, AcqRel);
+                    task.unpark();
+                }
+            }
+            // Either a notification is in progress, which may or may not
+            // have seen the previous task, or another thread is registering
+            // concurrently. In both cases it's safest to unpark the task right
+            // away so it gets a chance to look at the state again.
+            Err(_) => task.unpark(),
+        }
+    }
+
+    /// Notifies the task that last called `register`.
+    ///
+    /// The registered task is only notified once: if `register` hasn't been
+    /// called again since the last notification, then this does nothing.
+    pub fn notify(&self) {
+        // If the state wasn't `WAITING` then either a task is being
+        // registered concurrently, which will see our flag and unpark itself,
+        // or another notification is already in progress.
+        if self.state.fetch_or(NOTIFYING, AcqRel) == WAITING {
+            // We have exclusive access to the slot, take the task out.
+            let task = unsafe { (*self.task.get()).take() };
+            self.state.fetch_and(!NOTIFYING, Release);
+            if let Some(task) = task {
+                task.unpark();
+            }
+        }
+    }
+}
+
+impl Default for AtomicTask {
+    fn default() -> AtomicTask {
+        AtomicTask::new()
+    }
+}
+
+impl fmt::Debug for AtomicTask {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "AtomicTask")
+    }
+}
+
+unsafe impl Send for AtomicTask {}
+unsafe impl Sync for AtomicTask {}
@@ -0,0 +1,174 @@
+use std::prelude::v1::*;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use {Async, Future, Poll};
+use task::{self, Task};
+
+/// A barrier enabling a fixed number of tasks to rendezvous.
+///
+/// Each participant calls `wait` and polls the returned future, which
+/// resolves once all of them have arrived. Exactly one of the participants is
+/// told that it's the leader. The barrier can be reused after all
+/// participants have been released.
+///
+/// A `Barrier` is a cheaply cloneable handle, and all clones refer to the
+/// same barrier.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use futures::Future;
+/// use futures::sync::Barrier;
+///
+/// let barrier = Barrier::new(3);
+/// let threads = (0..3).map(|_| {
+///     let barrier = barrier.clone();
+///     thread::spawn(move || barrier.wait().wait().unwrap().is_leader())
+/// }).collect::<Vec<_>>();
+///
+/// let leaders = threads.into_iter()
+///                      .map(|t| t.join().unwrap())
+///                      .filter(|&leader| leader)
+///                      .count();
+/// assert_eq!(leaders, 1);
+/// ```
+#[derive(Clone)]
+pub struct Barrier {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    n: usize,
+    state: Mutex<State>,
+}
+
+struct State {
+    // Number of participants which have arrived in the current generation.
+    arrived: usize,
+
+    // Incremented every time all participants arrive and are released.
+    generation: usize,
+
+    // Tasks of the participants waiting in the current generation.
+    tasks: HashMap<usize, Task>,
+    next_id: usize,
+}
+
+impl Barrier {
+    /// Creates a new barrier which releases waiting tasks once `n` of them
+    /// have arrived.
+    ///
+    /// A barrier for zero participants behaves like one for a single
+    /// participant, releasing every waiter immediately.
+    pub fn new(n: usize) -> Barrier {
+        Barrier {
+            inner: Arc::new(Inner {
+                n: n,
+                state: Mutex::new(State {
+                    arrived: 0,
+                    generation: 0,
+                    tasks: HashMap::new(),
+                    next_id: 0,
+                }),
+            }),
+        }
+    }
+
+    /// Returns a future which arrives at the barrier when first polled, and
+    /// resolves once all participants have arrived.
+    ///
+    /// The returned future never resolves to an error. Dropping it after it
+    /// has arrived but before it resolves withdraws from the barrier again.
+    pub fn wait(&self) -> BarrierWait {
+        BarrierWait {
+            inner: self.inner.clone(),
+            arrival: None,
+        }
+    }
+}
+
+/// Future returned by `Barrier::wait` which resolves once all participants
+/// have arrived at the barrier.
+#[must_use = "futures do nothing unless polled"]
+pub struct BarrierWait {
+    inner: Arc<Inner>,
+
+    // The generation we arrived in and our id within it, once we've arrived.
+    arrival: Option<(usize, usize)>,
+}
+
+/// The result of waiting on a `Barrier`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BarrierWaitResult {
+    is_leader: bool,
+}
+
+impl BarrierWaitResult {
+    /// Returns whether this participant was the leader, which is the last one
+    /// to arrive at the barrier. Exactly one participant is the leader for
+    /// each time the barrier releases its waiters.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+}
+
+impl Future for BarrierWait {
+    type Item = BarrierWaitResult;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<BarrierWaitResult, ()> {
+        let me = task::park();
+        let tasks = {
+            let mut state = self.inner.state.lock().unwrap();
+            match self.arrival {
+                Some((generation, _)) if generation != state.generation => {
+                    self.arrival = None;
+                    return Ok(Async::Ready(BarrierWaitResult { is_leader: false }))
+                }
+                Some((_, id)) => {
+                    // Still waiting, make sure the most recent task is the
+                    // one which gets woken up.
+                    state.tasks.insert(id, me);
+                    return Ok(Async::NotReady)
+                }
+                None => {}
+            }
+
+            state.arrived += 1;
+            if state.arrived < self.inner.n {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.tasks.insert(id, me);
+                self.arrival = Some((state.generation, id));
+                return Ok(Async::NotReady)
+            }
+
+            // We're the last to arrive, so release everyone and start a new
+            // generation.
+            state.arrived = 0;
+            state.generation = state.generation.wrapping_add(1);
+            state.tasks.drain().map(|(_, task)| task).collect::<Vec<_>>()
+        };
+
+        // Unpark outside of the lock, the tasks may run immediately elsewhere.
+        for task in tasks {
+            task.unpark();
+        }
+        Ok(Async::Ready(BarrierWaitResult { is_leader: true }))
+    }
+}
+
+impl Drop for BarrierWait {
+    fn drop(&mut self) {
+        if let Some((generation, id)) = self.arrival {
+            let mut state = self.inner.state.lock().unwrap();
+            if generation == state.generation {
+                state.arrived -= 1;
+                state.tasks.remove(&id);
+            }
+        }
+    }
+}
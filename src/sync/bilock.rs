@@ -1,6 +1,10 @@
+use std::any::Any;
 use std::boxed::Box;
 use std::cell::UnsafeCell;
+use std::error::Error;
+use std::fmt;
 use std::mem;
+use std::ptr;
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
@@ -129,6 +133,21 @@ impl<T> BiLock<T> {
         }
     }
 
+    /// Attempts to put the two "halves" of a `BiLock<T>` back together and
+    /// recover the original value. Succeeds only if the two `BiLock<T>`s
+    /// originated from the same call to `BiLock::new`.
+    pub fn reunite(self, other: BiLock<T>) -> Result<T, ReuniteError<T>> {
+        if &*self.inner as *const Inner<T> == &*other.inner as *const Inner<T> {
+            drop(other);
+            let inner = Arc::try_unwrap(self.inner)
+                .ok()
+                .expect("futures: try_unwrap failed in BiLock<T>::reunite");
+            Ok(inner.into_inner())
+        } else {
+            Err(ReuniteError(self, other))
+        }
+    }
+
     fn unlock(&self) {
         match self.inner.state.swap(0, SeqCst) {
             // we've locked the lock, shouldn't be possible for us to see an
@@ -147,6 +166,20 @@ impl<T> BiLock<T> {
     }
 }
 
+impl<T> Inner<T> {
+    fn into_inner(self) -> T {
+        // Both handles are gone, so nobody can be holding the lock or be
+        // parked on it.
+        assert_eq!(self.state.load(SeqCst), 0);
+
+        // We can't move out of `self` as it implements `Drop`, so read the
+        // value out and forget the rest, which has nothing else to drop.
+        let value = unsafe { ptr::read(self.inner.get()) };
+        mem::forget(self);
+        value
+    }
+}
+
 impl<T> Drop for Inner<T> {
     fn drop(&mut self) {
         assert_eq!(self.state.load(SeqCst), 0);
@@ -241,3 +274,29 @@ impl<T> Drop for BiLockAcquired<T> {
         self.inner.unlock();
     }
 }
+
+/// Error indicating two `BiLock<T>`s were not two halves of a whole, and
+/// thus could not be `reunite`d.
+pub struct ReuniteError<T>(pub BiLock<T>, pub BiLock<T>);
+
+impl<T> fmt::Debug for ReuniteError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("ReuniteError")
+            .field(&"...")
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for ReuniteError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "tried to reunite two BiLocks that don't form a pair")
+    }
+}
+
+impl<T> Error for ReuniteError<T>
+    where T: Any
+{
+    fn description(&self) -> &str {
+        "tried to reunite two BiLocks that don't form a pair"
+    }
+}
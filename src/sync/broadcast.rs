@@ -0,0 +1,324 @@
+//! A multi-producer, multi-consumer, futures-aware broadcast channel.
+//!
+//! Every message sent on a broadcast channel is delivered to every `Receiver`
+//! which exists at the time it's sent. Receivers implement `Stream` and can be
+//! cloned to create new subscribers which pick up from the same position, and
+//! `Sender::subscribe` creates subscribers which only see messages sent from
+//! then on.
+//!
+//! # Lagging
+//!
+//! Messages are kept in a ring buffer of fixed capacity, and sending never
+//! waits for slow receivers. Instead, once the buffer is full the oldest
+//! message is overwritten. A receiver which falls so far behind that messages
+//! it hasn't yet seen are overwritten will yield a `Lagged` error reporting
+//! how many messages it missed, after which it carries on from the oldest
+//! message still in the buffer.
+//!
+//! # Disconnection
+//!
+//! When all `Sender` handles have been dropped, receivers yield the remaining
+//! messages in the buffer and then terminate. Sending a message while there
+//! are no receivers fails, handing the message back in a `SendError`.
+
+use std::prelude::v1::*;
+
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use task::{self, Task};
+use {Async, AsyncSink, Poll, StartSend, Sink, Stream};
+
+/// The transmission end of a broadcast channel.
+///
+/// This is created by the `channel` function, and implements `Sink`.
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+/// The receiving end of a broadcast channel which implements the `Stream`
+/// trait.
+///
+/// This is created by the `channel` function, `Sender::subscribe`, or by
+/// cloning another `Receiver`.
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+
+    // Identifies this receiver's parked task in the shared state.
+    id: usize,
+
+    // Position of the next message this receiver will yield.
+    next: u64,
+}
+
+/// Error type for sending, used when there are no receivers left to deliver
+/// the message to.
+pub struct SendError<T>(T);
+
+/// Error yielded by a `Receiver` which fell behind and missed messages.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Lagged(u64);
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    // The messages still available to receivers, oldest first.
+    buffer: VecDeque<T>,
+
+    // Maximum number of messages held in `buffer`.
+    capacity: usize,
+
+    // Position of the oldest message in `buffer`. Positions increase by one
+    // for every message sent, and never wrap in practice.
+    head: u64,
+
+    num_senders: usize,
+    num_receivers: usize,
+
+    // Tasks blocked waiting for a new message, keyed by receiver id.
+    rx_tasks: HashMap<usize, Task>,
+    next_id: usize,
+}
+
+/// Creates a new broadcast channel, returning the sender and a first receiver.
+///
+/// The channel keeps the last `capacity` messages sent, and sending another
+/// one overwrites the oldest whether or not every receiver has seen it yet.
+/// A receiver which misses messages this way yields a `Lagged` error the next
+/// time it's polled, see the module documentation for details.
+///
+/// # Panics
+///
+/// This function panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream};
+/// use futures::sync::broadcast;
+///
+/// let (tx, rx1) = broadcast::channel(16);
+/// let rx2 = rx1.clone();
+///
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+/// drop(tx);
+///
+/// assert_eq!(rx1.collect().wait(), Ok(vec![1, 2]));
+/// assert_eq!(rx2.collect().wait(), Ok(vec![1, 2]));
+/// ```
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "broadcast channel capacity must be at least one");
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            buffer: VecDeque::with_capacity(capacity),
+            capacity: capacity,
+            head: 0,
+            num_senders: 1,
+            num_receivers: 1,
+            rx_tasks: HashMap::new(),
+            next_id: 1,
+        }),
+    });
+    let receiver = Receiver {
+        inner: inner.clone(),
+        id: 0,
+        next: 0,
+    };
+    let sender = Sender {
+        inner: inner,
+    };
+    (sender, receiver)
+}
+
+impl<T> Sender<T> {
+    /// Sends a message to all receivers currently subscribed to the channel.
+    ///
+    /// This never waits: if the buffer is full then the oldest message in it
+    /// is dropped, and receivers which hadn't seen it yet will observe a
+    /// `Lagged` error. An error is returned if there are no receivers.
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        let tasks = {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.num_receivers == 0 {
+                return Err(SendError(msg))
+            }
+            if state.buffer.len() == state.capacity {
+                state.buffer.pop_front();
+                state.head += 1;
+            }
+            state.buffer.push_back(msg);
+            state.rx_tasks.drain().map(|(_, task)| task).collect::<Vec<_>>()
+        };
+
+        // Unpark outside of the lock, the tasks may run immediately elsewhere.
+        for task in tasks {
+            task.unpark();
+        }
+        Ok(())
+    }
+
+    /// Creates a new receiver which will see all messages sent after this
+    /// call.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut state = self.inner.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.num_receivers += 1;
+        Receiver {
+            inner: self.inner.clone(),
+            id: id,
+            next: state.head + state.buffer.len() as u64,
+        }
+    }
+
+    /// Returns the number of receivers currently subscribed to the channel.
+    pub fn receiver_count(&self) -> usize {
+        self.inner.state.lock().unwrap().num_receivers
+    }
+}
+
+impl<T> Sink for Sender<T> {
+    type SinkItem = T;
+    type SinkError = SendError<T>;
+
+    fn start_send(&mut self, msg: T) -> StartSend<T, SendError<T>> {
+        try!(Sender::send(self, msg));
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.inner.state.lock().unwrap().num_senders += 1;
+        Sender { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let tasks = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.num_senders -= 1;
+            if state.num_senders > 0 {
+                return
+            }
+            state.rx_tasks.drain().map(|(_, task)| task).collect::<Vec<_>>()
+        };
+
+        // Wake up all receivers so they can see that the channel is closed.
+        for task in tasks {
+            task.unpark();
+        }
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = T;
+    type Error = Lagged;
+
+    fn poll(&mut self) -> Poll<Option<T>, Lagged> {
+        let me = task::park();
+        let mut state = self.inner.state.lock().unwrap();
+
+        // Messages we haven't seen yet were overwritten, so skip ahead to the
+        // oldest one left and report how many were lost.
+        if self.next < state.head {
+            let skipped = state.head - self.next;
+            self.next = state.head;
+            return Err(Lagged(skipped))
+        }
+
+        let idx = (self.next - state.head) as usize;
+        if let Some(msg) = state.buffer.get(idx) {
+            self.next += 1;
+            return Ok(Async::Ready(Some(msg.clone())))
+        }
+
+        if state.num_senders == 0 {
+            Ok(Async::Ready(None))
+        } else {
+            state.rx_tasks.insert(self.id, me);
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let mut state = self.inner.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.num_receivers += 1;
+        Receiver {
+            inner: self.inner.clone(),
+            id: id,
+            next: self.next,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.num_receivers -= 1;
+        state.rx_tasks.remove(&self.id);
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("SendError")
+            .field(&"...")
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "send failed because there are no receivers")
+    }
+}
+
+impl<T> Error for SendError<T>
+    where T: Any
+{
+    fn description(&self) -> &str {
+        "send failed because there are no receivers"
+    }
+}
+
+impl<T> SendError<T> {
+    /// Returns the message that was attempted to be sent but failed.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl Lagged {
+    /// Returns the number of messages the receiver missed.
+    pub fn skipped(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Lagged {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "receiver lagged behind and missed {} messages", self.0)
+    }
+}
+
+impl Error for Lagged {
+    fn description(&self) -> &str {
+        "receiver lagged behind and missed messages"
+    }
+}
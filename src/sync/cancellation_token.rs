@@ -0,0 +1,200 @@
+use std::prelude::v1::*;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+use {Async, Future, Poll};
+use task::{self, Task};
+
+/// A token which can be used to signal cancellation to a group of tasks.
+///
+/// Cancelling a token with `cancel` wakes up every task waiting on a
+/// `cancelled` future, and makes `is_cancelled` return `true`. Tokens can be
+/// arranged in a tree with `child_token`: cancelling a token also cancels all
+/// of its children, while cancelling a child doesn't affect its parent.
+///
+/// A `CancellationToken` is a cheaply cloneable handle, and all clones refer
+/// to the same token.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use futures::Future;
+/// use futures::sync::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let child = token.child_token();
+/// let t = thread::spawn(move || child.cancelled().wait());
+///
+/// token.cancel();
+/// t.join().unwrap().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<Node>,
+}
+
+struct Node {
+    state: Mutex<State>,
+}
+
+struct State {
+    cancelled: bool,
+
+    // Tasks waiting for this token to be cancelled, keyed by waiter id.
+    tasks: HashMap<usize, Task>,
+    next_id: usize,
+
+    // Child tokens, which are cancelled along with this one. Children don't
+    // keep their parent alive, and the parent doesn't keep its children
+    // alive.
+    children: Vec<Weak<Node>>,
+}
+
+impl Node {
+    fn new(cancelled: bool) -> Arc<Node> {
+        Arc::new(Node {
+            state: Mutex::new(State {
+                cancelled: cancelled,
+                tasks: HashMap::new(),
+                next_id: 0,
+                children: Vec::new(),
+            }),
+        })
+    }
+}
+
+impl CancellationToken {
+    /// Creates a new token which hasn't been cancelled.
+    pub fn new() -> CancellationToken {
+        CancellationToken {
+            inner: Node::new(false),
+        }
+    }
+
+    /// Creates a child token, which is cancelled whenever this token is.
+    ///
+    /// The child can also be cancelled on its own, without affecting this
+    /// token. If this token has already been cancelled then so is the
+    /// returned child.
+    pub fn child_token(&self) -> CancellationToken {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.cancelled {
+            return CancellationToken { inner: Node::new(true) }
+        }
+
+        // Prune children which have gone away before growing the list.
+        if state.children.len() == state.children.capacity() {
+            state.children.retain(|child| child.upgrade().is_some());
+        }
+
+        let child = Node::new(false);
+        state.children.push(Arc::downgrade(&child));
+        CancellationToken { inner: child }
+    }
+
+    /// Cancels this token and all of its children, waking up all the tasks
+    /// waiting for them to be cancelled.
+    ///
+    /// Cancelling a token which has already been cancelled does nothing.
+    pub fn cancel(&self) {
+        // Walk the tree with an explicit stack so that deep hierarchies don't
+        // overflow the real one.
+        let mut nodes = vec![self.inner.clone()];
+        while let Some(node) = nodes.pop() {
+            let (tasks, children) = {
+                let mut state = node.state.lock().unwrap();
+                if state.cancelled {
+                    continue
+                }
+                state.cancelled = true;
+                let tasks = state.tasks.drain().map(|(_, task)| task).collect::<Vec<_>>();
+                let children = state.children.drain(..).collect::<Vec<_>>();
+                (tasks, children)
+            };
+
+            // Unpark outside of the lock, the tasks may run immediately
+            // elsewhere.
+            for task in tasks {
+                task.unpark();
+            }
+            nodes.extend(children.iter().filter_map(|child| child.upgrade()));
+        }
+    }
+
+    /// Returns whether this token has been cancelled, either directly or
+    /// through one of its ancestors.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.state.lock().unwrap().cancelled
+    }
+
+    /// Returns a future which resolves once this token has been cancelled.
+    ///
+    /// The returned future never resolves to an error.
+    pub fn cancelled(&self) -> WaitForCancellation {
+        WaitForCancellation {
+            inner: self.inner.clone(),
+            id: None,
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> CancellationToken {
+        CancellationToken::new()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("CancellationToken")
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Future returned by `CancellationToken::cancelled` which resolves once the
+/// token has been cancelled.
+#[must_use = "futures do nothing unless polled"]
+pub struct WaitForCancellation {
+    inner: Arc<Node>,
+
+    // Our key in the token's `tasks` map, once we've registered a task.
+    id: Option<usize>,
+}
+
+impl Future for WaitForCancellation {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        let me = task::park();
+        let mut state = self.inner.state.lock().unwrap();
+        if state.cancelled {
+            self.id = None;
+            return Ok(Async::Ready(()))
+        }
+
+        let id = match self.id {
+            Some(id) => id,
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                self.id = Some(id);
+                id
+            }
+        };
+        state.tasks.insert(id, me);
+        Ok(Async::NotReady)
+    }
+}
+
+impl Drop for WaitForCancellation {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            self.inner.state.lock().unwrap().tasks.remove(&id);
+        }
+    }
+}
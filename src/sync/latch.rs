@@ -0,0 +1,138 @@
+use std::prelude::v1::*;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use {Async, Future, Poll};
+use task::{self, Task};
+
+/// A synchronization aid which lets tasks wait until a set of operations
+/// being performed elsewhere have completed.
+///
+/// The latch is created with a count, which is decremented by `count_down`.
+/// Futures returned by `wait` resolve once the count reaches zero, after which
+/// the latch stays open: it can't be reset.
+///
+/// A `CountDownLatch` is a cheaply cloneable handle, and all clones refer to
+/// the same latch.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use futures::Future;
+/// use futures::sync::CountDownLatch;
+///
+/// let latch = CountDownLatch::new(3);
+/// for _ in 0..3 {
+///     let latch = latch.clone();
+///     thread::spawn(move || latch.count_down());
+/// }
+///
+/// latch.wait().wait().unwrap();
+/// assert_eq!(latch.count(), 0);
+/// ```
+#[derive(Clone)]
+pub struct CountDownLatch {
+    inner: Arc<Mutex<LatchState>>,
+}
+
+struct LatchState {
+    count: usize,
+    tasks: HashMap<usize, Task>,
+    next_id: usize,
+}
+
+impl CountDownLatch {
+    /// Creates a new latch which opens once `count_down` has been called
+    /// `count` times.
+    pub fn new(count: usize) -> CountDownLatch {
+        CountDownLatch {
+            inner: Arc::new(Mutex::new(LatchState {
+                count: count,
+                tasks: HashMap::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    /// Decrements the count of the latch, waking up all waiting tasks if it
+    /// reaches zero.
+    ///
+    /// Once the count is zero this method does nothing.
+    pub fn count_down(&self) {
+        let tasks = {
+            let mut state = self.inner.lock().unwrap();
+            if state.count == 0 {
+                return
+            }
+            state.count -= 1;
+            if state.count > 0 {
+                return
+            }
+            state.tasks.drain().map(|(_, task)| task).collect::<Vec<_>>()
+        };
+
+        for task in tasks {
+            task.unpark();
+        }
+    }
+
+    /// Returns the current count of the latch.
+    pub fn count(&self) -> usize {
+        self.inner.lock().unwrap().count
+    }
+
+    /// Returns a future which resolves once the count of the latch reaches
+    /// zero.
+    ///
+    /// The returned future never resolves to an error.
+    pub fn wait(&self) -> CountDownLatchWait {
+        CountDownLatchWait {
+            inner: self.inner.clone(),
+            id: None,
+        }
+    }
+}
+
+/// Future returned by `CountDownLatch::wait` which resolves once the count of
+/// the latch reaches zero.
+#[must_use = "futures do nothing unless polled"]
+pub struct CountDownLatchWait {
+    inner: Arc<Mutex<LatchState>>,
+    id: Option<usize>,
+}
+
+impl Future for CountDownLatchWait {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        let me = task::park();
+        let mut state = self.inner.lock().unwrap();
+        if state.count == 0 {
+            self.id = None;
+            return Ok(Async::Ready(()))
+        }
+
+        let id = match self.id {
+            Some(id) => id,
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                self.id = Some(id);
+                id
+            }
+        };
+        state.tasks.insert(id, me);
+        Ok(Async::NotReady)
+    }
+}
+
+impl Drop for CountDownLatchWait {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            self.inner.lock().unwrap().tasks.remove(&id);
+        }
+    }
+}
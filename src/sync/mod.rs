@@ -7,6 +7,24 @@
 
 pub mod oneshot;
 pub mod mpsc;
+pub mod broadcast;
+pub mod watch;
+mod atomic_task;
+mod barrier;
 mod bilock;
+mod cancellation_token;
+mod latch;
+mod mutex;
+mod notify;
+mod rwlock;
+mod semaphore;
 
-pub use self::bilock::{BiLock, BiLockGuard, BiLockAcquire, BiLockAcquired};
+pub use self::atomic_task::AtomicTask;
+pub use self::barrier::{Barrier, BarrierWait, BarrierWaitResult};
+pub use self::bilock::{BiLock, BiLockGuard, BiLockAcquire, BiLockAcquired, ReuniteError};
+pub use self::cancellation_token::{CancellationToken, WaitForCancellation};
+pub use self::latch::{CountDownLatch, CountDownLatchWait};
+pub use self::mutex::{Mutex, MutexGuard, MutexAcquire};
+pub use self::notify::{Notify, Notified};
+pub use self::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard, RwLockRead, RwLockWrite};
+pub use self::semaphore::{Semaphore, SemaphorePermit, SemaphoreAcquire};
@@ -92,7 +92,7 @@ pub struct Sender<T> {
     // Handle to the task that is blocked on this sender. This handle is sent
     // to the receiver half in order to be notified when the sender becomes
     // unblocked.
-    sender_task: SenderTask,
+    sender_task: Arc<Mutex<SenderTask>>,
 
     // True if the sender might be blocked. This is an optimization to avoid
     // having to lock the mutex most of the time.
@@ -153,6 +153,103 @@ impl<T> SendError<T> {
     }
 }
 
+/// Error type returned from `try_recv`, used when no message could be
+/// received immediately.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TryRecvError {
+    /// The channel is currently empty, but may receive more messages.
+    Empty,
+
+    /// All senders are gone and every message has been received.
+    Disconnected,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.description_str())
+    }
+}
+
+impl Error for TryRecvError {
+    fn description(&self) -> &str {
+        self.description_str()
+    }
+}
+
+impl TryRecvError {
+    fn description_str(&self) -> &'static str {
+        match *self {
+            TryRecvError::Empty => "receive failed because channel is empty",
+            TryRecvError::Disconnected => "receive failed because all senders are gone",
+        }
+    }
+}
+
+/// Error type returned from `Sender::try_send`, used when the channel is
+/// either full or the receiving end is gone.
+pub struct TrySendError<T> {
+    kind: TrySendErrorKind<T>,
+}
+
+enum TrySendErrorKind<T> {
+    Full(T),
+    Disconnected(T),
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let kind = if self.is_full() { "Full" } else { "Disconnected" };
+        fmt.debug_struct("TrySendError")
+            .field("kind", &kind)
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.description_str())
+    }
+}
+
+impl<T> Error for TrySendError<T>
+    where T: Any
+{
+    fn description(&self) -> &str {
+        self.description_str()
+    }
+}
+
+impl<T> TrySendError<T> {
+    /// Returns true if this error is a result of the channel being full.
+    pub fn is_full(&self) -> bool {
+        match self.kind {
+            TrySendErrorKind::Full(_) => true,
+            TrySendErrorKind::Disconnected(_) => false,
+        }
+    }
+
+    /// Returns true if this error is a result of the receiver being dropped.
+    pub fn is_disconnected(&self) -> bool {
+        !self.is_full()
+    }
+
+    /// Returns the message that was attempted to be sent but failed.
+    pub fn into_inner(self) -> T {
+        match self.kind {
+            TrySendErrorKind::Full(msg) |
+            TrySendErrorKind::Disconnected(msg) => msg,
+        }
+    }
+
+    fn description_str(&self) -> &'static str {
+        if self.is_full() {
+            "send failed because channel is full"
+        } else {
+            "send failed because receiver is gone"
+        }
+    }
+}
+
 struct Inner<T> {
     // Max buffer size of the channel. If `None` then the channel is unbounded.
     buffer: Option<usize>,
@@ -165,7 +262,7 @@ struct Inner<T> {
     message_queue: Queue<Option<T>>,
 
     // Atomic, FIFO queue used to send parked task handles to the receiver.
-    parked_queue: Queue<SenderTask>,
+    parked_queue: Queue<Arc<Mutex<SenderTask>>>,
 
     // Number of senders in existence
     num_senders: AtomicUsize,
@@ -184,6 +281,31 @@ struct State {
     num_messages: usize,
 }
 
+// Sent to the consumer to wake up blocked producers
+struct SenderTask {
+    // The task to notify once the sender is unparked. This is `None` if the
+    // sender was parked outside of a task, for example by `try_send`.
+    task: Option<Task>,
+
+    // Whether the sender is waiting on the receiver to make room for it.
+    is_parked: bool,
+}
+
+impl SenderTask {
+    fn new() -> SenderTask {
+        SenderTask {
+            task: None,
+            is_parked: false,
+        }
+    }
+
+    // Flags the sender as unparked, returning the task to notify.
+    fn unpark(&mut self) -> Option<Task> {
+        self.is_parked = false;
+        self.task.take()
+    }
+}
+
 struct ReceiverTask {
     unparked: bool,
     task: Option<Task>,
@@ -210,9 +332,6 @@ const MAX_CAPACITY: usize = !(OPEN_MASK);
 // a channel. This is because each sender gets a guaranteed slot.
 const MAX_BUFFER: usize = MAX_CAPACITY >> 1;
 
-// Sent to the consumer to wake up blocked producers
-type SenderTask = Arc<Mutex<Option<Task>>>;
-
 /// Creates an in-memory channel implementation of the `Stream` trait with
 /// bounded capacity.
 ///
@@ -265,7 +384,7 @@ fn channel2<T>(buffer: Option<usize>) -> (Sender<T>, Receiver<T>) {
 
     let tx = Sender {
         inner: inner.clone(),
-        sender_task: Arc::new(Mutex::new(None)),
+        sender_task: Arc::new(Mutex::new(SenderTask::new())),
         maybe_parked: false,
     };
 
@@ -283,6 +402,80 @@ fn channel2<T>(buffer: Option<usize>) -> (Sender<T>, Receiver<T>) {
  */
 
 impl<T> Sender<T> {
+    /// Attempts to send a message on this `Sender` without blocking.
+    ///
+    /// This function, unlike `start_send`, is safe to call whether it's being
+    /// called on a task or not. Note that this function, however, will *not*
+    /// attempt to block the current task if the message cannot be sent.
+    ///
+    /// It is not recommended to call this function from inside of a future,
+    /// only from an external thread where you've otherwise arranged to be
+    /// notified when the channel is no longer full.
+    pub fn try_send(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        // If the sender is currently blocked, reject the message before doing
+        // any work.
+        if !self.poll_unparked(false).is_ready() {
+            return Err(TrySendError {
+                kind: TrySendErrorKind::Full(msg),
+            })
+        }
+
+        // The channel has capacity to accept the message, so send it. If this
+        // fills up the channel then the sender is parked without a task, and
+        // further messages are rejected until the receiver makes room.
+        self.do_send(Some(msg), false).map_err(|e| {
+            TrySendError {
+                kind: TrySendErrorKind::Disconnected(e.into_inner()),
+            }
+        })
+    }
+
+    /// Returns whether the channel has been closed, either by the receiving
+    /// end being dropped or by `Receiver::close`, in which case any further
+    /// sends will fail.
+    pub fn is_closed(&self) -> bool {
+        !decode_state(self.inner.state.load(SeqCst)).is_open
+    }
+
+    /// Returns the number of messages currently buffered in the channel.
+    pub fn len(&self) -> usize {
+        decode_state(self.inner.state.load(SeqCst)).num_messages
+    }
+
+    /// Returns whether there are no messages currently buffered in the
+    /// channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of messages the channel can currently hold before
+    /// senders have to wait.
+    ///
+    /// As every sender has a guaranteed slot in the channel, this is the
+    /// `buffer` the channel was created with plus the number of senders.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Polls the channel to determine if there is guaranteed capacity to send
+    /// at least one item without waiting.
+    ///
+    /// Returns `Ok(Async::Ready(()))` if the next call to `start_send` or
+    /// `try_send` will accept a message, so that the message doesn't need to
+    /// be built until there's room for it. Otherwise the current task is
+    /// scheduled to be notified once there is, and `Ok(Async::NotReady)` is
+    /// returned.
+    ///
+    /// An error is returned if the receiving end has been dropped or closed.
+    pub fn poll_ready(&mut self) -> Poll<(), SendError<()>> {
+        let state = decode_state(self.inner.state.load(SeqCst));
+        if !state.is_open {
+            return Err(SendError(()))
+        }
+
+        Ok(self.poll_unparked(true))
+    }
+
     // Do the send without failing
     fn do_send(&mut self, msg: Option<T>, can_park: bool) -> Result<(), SendError<T>> {
         // First, increment the number of messages contained by the channel.
@@ -351,11 +544,13 @@ impl<T> Sender<T> {
             assert!(state.num_messages < MAX_CAPACITY, "buffer space exhausted; \
                     sending this messages would overflow the state");
 
-            state.num_messages += 1;
-
-            // The channel is closed by all sender handles being dropped.
+            // The channel is closed by all sender handles being dropped. The
+            // termination message isn't counted, so that `num_messages`
+            // always reflects the number of actual messages in the channel.
             if close {
                 state.is_open = false;
+            } else {
+                state.num_messages += 1;
             }
 
             let next = encode_state(&state);
@@ -414,21 +609,27 @@ impl<T> Sender<T> {
         };
 
         self.maybe_parked = true;
-        *self.sender_task.lock().unwrap() = task;
+        {
+            let mut sender_task = self.sender_task.lock().unwrap();
+            sender_task.task = task;
+            sender_task.is_parked = true;
+        }
 
         // Send handle over queue
         let t = self.sender_task.clone();
         self.inner.parked_queue.push(t);
     }
 
-    fn poll_unparked(&mut self) -> Async<()> {
+    // Checks whether the receiver has made room for this sender. If not, and
+    // `do_park` is set, the current task is registered to be notified.
+    fn poll_unparked(&mut self, do_park: bool) -> Async<()> {
         // First check the `maybe_parked` variable. This avoids acquiring the
         // lock in most cases
         if self.maybe_parked {
             // Get a lock on the task handle
             let mut task = self.sender_task.lock().unwrap();
 
-            if task.is_none() {
+            if !task.is_parked {
                 self.maybe_parked = false;
                 return Async::Ready(())
             }
@@ -439,7 +640,9 @@ impl<T> Sender<T> {
             //
             // Update the task in case the `Sender` has been moved to another
             // task
-            *task = Some(task::park());
+            if do_park {
+                task.task = Some(task::park());
+            }
 
             Async::NotReady
         } else {
@@ -455,7 +658,7 @@ impl<T> Sink for Sender<T> {
     fn start_send(&mut self, msg: T) -> StartSend<T, SendError<T>> {
         // If the sender is currently blocked, reject the message before doing
         // any work.
-        if !self.poll_unparked().is_ready() {
+        if !self.poll_unparked(true).is_ready() {
             return Ok(AsyncSink::NotReady(msg));
         }
 
@@ -468,6 +671,13 @@ impl<T> Sink for Sender<T> {
     fn poll_complete(&mut self) -> Poll<(), SendError<T>> {
         Ok(Async::Ready(()))
     }
+
+    fn close(&mut self) -> Poll<(), SendError<T>> {
+        // Messages are handed to the receiver as soon as they're sent, and
+        // the receiver only sees the end of the stream once every sender has
+        // been dropped, so there's nothing left to do here.
+        Ok(Async::Ready(()))
+    }
 }
 
 impl<T> UnboundedSender<T> {
@@ -483,6 +693,24 @@ impl<T> UnboundedSender<T> {
             Err(e) => Err(e),
         }
     }
+
+    /// Returns whether the channel has been closed, either by the receiving
+    /// end being dropped or by `UnboundedReceiver::close`, in which case any
+    /// further sends will fail.
+    pub fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+    /// Returns the number of messages currently buffered in the channel.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no messages currently buffered in the
+    /// channel.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 impl<T> Sink for UnboundedSender<T> {
@@ -496,6 +724,10 @@ impl<T> Sink for UnboundedSender<T> {
     fn poll_complete(&mut self) -> Poll<(), SendError<T>> {
         self.0.poll_complete()
     }
+
+    fn close(&mut self) -> Poll<(), SendError<T>> {
+        self.0.close()
+    }
 }
 
 impl<T> Clone for UnboundedSender<T> {
@@ -528,7 +760,7 @@ impl<T> Clone for Sender<T> {
             if actual == curr {
                 return Sender {
                     inner: self.inner.clone(),
-                    sender_task: Arc::new(Mutex::new(None)),
+                    sender_task: Arc::new(Mutex::new(SenderTask::new())),
                     maybe_parked: false,
                 };
             }
@@ -584,7 +816,7 @@ impl<T> Receiver<T> {
         loop {
             match unsafe { self.inner.parked_queue.pop() } {
                 PopResult::Data(task) => {
-                    let task = task.lock().unwrap().take();
+                    let task = task.lock().unwrap().unpark();
                     if let Some(task) = task {
                         task.unpark();
                     }
@@ -624,6 +856,67 @@ impl<T> Receiver<T> {
         }
     }
 
+    /// Attempts to receive a message outside of the context of a task.
+    ///
+    /// Unlike `poll`, this does not schedule the current task to be notified
+    /// when a message arrives, so it can be used to synchronously drain a
+    /// receiver, for example during shutdown. An error is returned if there
+    /// is no message available right now, distinguishing whether more may
+    /// still arrive or the channel has been closed and fully drained.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        match self.next_message() {
+            Async::Ready(msg) => {
+                match self.finish_message(msg) {
+                    Some(msg) => Ok(msg),
+                    None => Err(TryRecvError::Disconnected),
+                }
+            }
+            Async::NotReady => {
+                let state = decode_state(self.inner.state.load(SeqCst));
+                if !state.is_open && state.num_messages == 0 {
+                    Err(TryRecvError::Disconnected)
+                } else {
+                    Err(TryRecvError::Empty)
+                }
+            }
+        }
+    }
+
+    /// Returns the number of messages currently buffered in the channel.
+    pub fn len(&self) -> usize {
+        decode_state(self.inner.state.load(SeqCst)).num_messages
+    }
+
+    /// Returns whether there are no messages currently buffered in the
+    /// channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of messages the channel can currently hold before
+    /// senders have to wait.
+    ///
+    /// As every sender has a guaranteed slot in the channel, this is the
+    /// `buffer` the channel was created with plus the number of senders.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    // Does the bookkeeping for a message popped off of the message queue.
+    fn finish_message(&mut self, msg: Option<T>) -> Option<T> {
+        // If there are any parked task handles in the parked queue, pop
+        // one and unpark it.
+        self.unpark_one();
+
+        // Decrement number of messages, the termination message isn't
+        // counted.
+        if msg.is_some() {
+            self.dec_num_messages();
+        }
+
+        msg
+    }
+
     // Unpark a single task handle if there is one pending in the parked queue
     fn unpark_one(&mut self) {
         loop {
@@ -631,7 +924,7 @@ impl<T> Receiver<T> {
                 PopResult::Data(task) => {
                     // Do this step first so that the lock is dropped when
                     // `unpark` is called
-                    let task = task.lock().unwrap().take();
+                    let task = task.lock().unwrap().unpark();
 
                     if let Some(task) = task {
                         task.unpark();
@@ -725,15 +1018,8 @@ impl<T> Stream for Receiver<T> {
                 }
             };
 
-            // If there are any parked task handles in the parked queue, pop
-            // one and unpark it.
-            self.unpark_one();
-
-            // Decrement number of messages
-            self.dec_num_messages();
-
             // Return the message
-            return Ok(Async::Ready(msg));
+            return Ok(Async::Ready(self.finish_message(msg)));
         }
     }
 }
@@ -756,6 +1042,24 @@ impl<T> UnboundedReceiver<T> {
     pub fn close(&mut self) {
         self.0.close();
     }
+
+    /// Attempts to receive a message outside of the context of a task.
+    ///
+    /// See `Receiver::try_recv` for more details.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        self.0.try_recv()
+    }
+
+    /// Returns the number of messages currently buffered in the channel.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no messages currently buffered in the
+    /// channel.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 impl<T> Stream for UnboundedReceiver<T> {
@@ -774,6 +1078,12 @@ impl<T> Stream for UnboundedReceiver<T> {
  */
 
 impl<T> Inner<T> {
+    // Only called for bounded channels, the unbounded wrappers don't expose
+    // a capacity.
+    fn capacity(&self) -> usize {
+        self.buffer.unwrap() + self.num_senders.load(SeqCst)
+    }
+
     // The return value is such that the total number of messages that can be
     // enqueued into the channel will never exceed MAX_CAPACITY
     fn max_senders(&self) -> usize {
@@ -0,0 +1,269 @@
+use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::{self, Arc};
+
+use {Async, Future, Poll};
+use task::{self, Task};
+
+/// A futures-aware mutual exclusion lock, shared between any number of tasks.
+///
+/// Unlike `std::sync::Mutex`, acquiring this lock never blocks the current
+/// thread. Instead the `lock` method returns a future which resolves to a
+/// `MutexGuard` once the lock has been acquired, parking the current task in
+/// the meantime.
+///
+/// Tasks waiting for the lock are queued in the order in which they first
+/// polled their `MutexAcquire` future, and when the lock is released it is
+/// handed directly to the task at the front of the queue. This means that a
+/// steady stream of new lockers can't starve the tasks which are already
+/// waiting.
+///
+/// A `Mutex` is a cheaply cloneable handle to the protected data, similar to
+/// an `Arc<Mutex<T>>`, and the guards it hands out own a reference to the
+/// data as well, so they can be held across any number of future
+/// combinators.
+pub struct Mutex<T> {
+    inner: Arc<Inner<T>>,
+}
+
+struct Inner<T> {
+    state: sync::Mutex<State>,
+    data: UnsafeCell<T>,
+}
+
+struct State {
+    // Whether the lock is currently held, or has been handed to a waiter who
+    // has yet to pick it up.
+    locked: bool,
+
+    // The ticket of the waiter the lock has been handed to, if any.
+    handoff: Option<usize>,
+
+    // Tasks waiting to acquire the lock, in order of arrival.
+    waiters: VecDeque<Waiter>,
+
+    next_ticket: usize,
+}
+
+struct Waiter {
+    ticket: usize,
+    task: Task,
+}
+
+unsafe impl<T: Send> Send for Inner<T> {}
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+impl<T> Mutex<T> {
+    /// Creates a new mutex in an unlocked state ready for use.
+    pub fn new(t: T) -> Mutex<T> {
+        Mutex {
+            inner: Arc::new(Inner {
+                state: sync::Mutex::new(State {
+                    locked: false,
+                    handoff: None,
+                    waiters: VecDeque::new(),
+                    next_ticket: 0,
+                }),
+                data: UnsafeCell::new(t),
+            }),
+        }
+    }
+
+    /// Returns a future which will resolve to a guard for the protected data
+    /// once the lock has been acquired.
+    ///
+    /// The returned future never resolves to an error. Dropping it before it
+    /// resolves gives up its place in the queue of waiters.
+    pub fn lock(&self) -> MutexAcquire<T> {
+        MutexAcquire {
+            inner: Some(self.inner.clone()),
+            ticket: None,
+        }
+    }
+
+    /// Attempts to acquire this lock immediately, without waiting.
+    ///
+    /// If the lock is free and no other task is waiting for it then a guard
+    /// is returned. Otherwise `None` is returned and the current task is *not*
+    /// scheduled to receive a notification, so this method may be called
+    /// outside the context of a task.
+    pub fn try_lock(&self) -> Option<MutexGuard<T>> {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.locked {
+            None
+        } else {
+            state.locked = true;
+            Some(MutexGuard::new(self.inner.clone()))
+        }
+    }
+}
+
+impl<T> Clone for Mutex<T> {
+    fn clone(&self) -> Mutex<T> {
+        Mutex { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Inner<T> {
+    // Releases the lock, handing it to the first waiter in line if there is
+    // one.
+    fn unlock(&self) {
+        let task = {
+            let mut state = self.state.lock().unwrap();
+            debug_assert!(state.locked);
+            debug_assert!(state.handoff.is_none());
+            match state.waiters.pop_front() {
+                Some(waiter) => {
+                    state.handoff = Some(waiter.ticket);
+                    Some(waiter.task)
+                }
+                None => {
+                    state.locked = false;
+                    None
+                }
+            }
+        };
+
+        // Unpark outside of the lock, the task may run immediately elsewhere.
+        if let Some(task) = task {
+            task.unpark();
+        }
+    }
+}
+
+/// Future returned by `Mutex::lock` which resolves to a `MutexGuard` once the
+/// lock has been acquired.
+#[must_use = "futures do nothing unless polled"]
+pub struct MutexAcquire<T> {
+    inner: Option<Arc<Inner<T>>>,
+
+    // Our place in line, assigned the first time we fail to get the lock.
+    ticket: Option<usize>,
+}
+
+impl<T> Future for MutexAcquire<T> {
+    type Item = MutexGuard<T>;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<MutexGuard<T>, ()> {
+        let me = task::park();
+        let acquired = {
+            let inner = self.inner.as_ref().expect("cannot poll MutexAcquire twice");
+            let mut state = inner.state.lock().unwrap();
+            match self.ticket {
+                None if !state.locked => {
+                    state.locked = true;
+                    true
+                }
+                None => {
+                    let ticket = state.next_ticket;
+                    state.next_ticket = state.next_ticket.wrapping_add(1);
+                    state.waiters.push_back(Waiter {
+                        ticket: ticket,
+                        task: me,
+                    });
+                    self.ticket = Some(ticket);
+                    false
+                }
+                Some(ticket) if state.handoff == Some(ticket) => {
+                    state.handoff = None;
+                    true
+                }
+                Some(ticket) => {
+                    // Still waiting, make sure the most recent task is the
+                    // one which gets woken up.
+                    for waiter in state.waiters.iter_mut() {
+                        if waiter.ticket == ticket {
+                            waiter.task = me;
+                            break
+                        }
+                    }
+                    false
+                }
+            }
+        };
+
+        if acquired {
+            self.ticket = None;
+            Ok(Async::Ready(MutexGuard::new(self.inner.take().unwrap())))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+impl<T> Drop for MutexAcquire<T> {
+    fn drop(&mut self) {
+        let ticket = match self.ticket {
+            Some(ticket) => ticket,
+            None => return,
+        };
+        let inner = match self.inner {
+            Some(ref inner) => inner,
+            None => return,
+        };
+
+        let handed_off = {
+            let mut state = inner.state.lock().unwrap();
+            if state.handoff == Some(ticket) {
+                state.handoff = None;
+                true
+            } else {
+                state.waiters.retain(|w| w.ticket != ticket);
+                false
+            }
+        };
+
+        // We were given the lock but never picked it up, so pass it along to
+        // the next in line.
+        if handed_off {
+            inner.unlock();
+        }
+    }
+}
+
+/// An RAII guard returned by the `MutexAcquire` future and `Mutex::try_lock`.
+///
+/// This structure acts as a sentinel to the data in the `Mutex<T>` itself,
+/// implementing `Deref` and `DerefMut` to `T`. When dropped, the lock will be
+/// unlocked, waking up the next task waiting for it.
+pub struct MutexGuard<T> {
+    inner: Arc<Inner<T>>,
+
+    // Opt out of the automatic `Send` and `Sync` impls, which would otherwise
+    // let a guard hand out `&T` across threads for a `T` which isn't `Sync`.
+    _marker: PhantomData<*mut T>,
+}
+
+unsafe impl<T: Send> Send for MutexGuard<T> {}
+unsafe impl<T: Send + Sync> Sync for MutexGuard<T> {}
+
+impl<T> MutexGuard<T> {
+    fn new(inner: Arc<Inner<T>>) -> MutexGuard<T> {
+        MutexGuard {
+            inner: inner,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for MutexGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.inner.data.get() }
+    }
+}
+
+impl<T> DerefMut for MutexGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.inner.data.get() }
+    }
+}
+
+impl<T> Drop for MutexGuard<T> {
+    fn drop(&mut self) {
+        self.inner.unlock();
+    }
+}
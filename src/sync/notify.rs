@@ -0,0 +1,109 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::SeqCst;
+
+use {Async, Future, Poll};
+use sync::AtomicTask;
+
+/// A handle to wake up a task waiting on a notification, without sending any
+/// data along with it.
+///
+/// Calling `notify` stores a single permit, which is consumed by the next
+/// `Notified` future to be polled. If a permit is already stored then
+/// further calls to `notify` are coalesced with it, so a waiter is woken up
+/// at least once after any number of notifications.
+///
+/// Like `AtomicTask`, which it's built on, a `Notify` is meant to have one
+/// waiter at a time. It is a cheaply cloneable handle, so the producing and
+/// consuming halves can be moved to separate tasks.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use futures::Future;
+/// use futures::sync::Notify;
+///
+/// let notify = Notify::new();
+/// let notify2 = notify.clone();
+/// thread::spawn(move || {
+///     notify2.notify();
+/// });
+///
+/// notify.notified().wait().unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Notify {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    notified: AtomicBool,
+    task: AtomicTask,
+}
+
+impl Notify {
+    /// Creates a new `Notify` with no stored permit.
+    pub fn new() -> Notify {
+        Notify {
+            inner: Arc::new(Inner {
+                notified: AtomicBool::new(false),
+                task: AtomicTask::new(),
+            }),
+        }
+    }
+
+    /// Stores a permit and wakes up the task waiting on a `Notified` future,
+    /// if there is one.
+    pub fn notify(&self) {
+        self.inner.notified.store(true, SeqCst);
+        self.inner.task.notify();
+    }
+
+    /// Returns a future which resolves once a permit is available, consuming
+    /// it.
+    ///
+    /// If `notify` has been called since the last permit was consumed then
+    /// the future resolves immediately. The returned future never resolves to
+    /// an error.
+    pub fn notified(&self) -> Notified {
+        Notified {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Notify {
+        Notify::new()
+    }
+}
+
+/// Future returned by `Notify::notified` which resolves once the `Notify` has
+/// been notified.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Notified {
+    inner: Arc<Inner>,
+}
+
+impl Future for Notified {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        if self.inner.notified.swap(false, SeqCst) {
+            return Ok(Async::Ready(()))
+        }
+
+        // Register first and then check again, so a permit stored in between
+        // isn't missed.
+        self.inner.task.register();
+        if self.inner.notified.swap(false, SeqCst) {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
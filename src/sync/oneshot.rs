@@ -112,15 +112,60 @@ impl<T> Sender<T> {
     /// This function will consume `self` and indicate to the other end, the
     /// `Receiver`, that the error provided is the result of the computation this
     /// represents.
+    ///
+    /// If the `Receiver` has already gone away then the value is dropped. Use
+    /// `send` to get the value back in that case.
     pub fn complete(self, t: T) {
-        // First up, flag that this method was called and then store the data.
-        // Note that this lock acquisition should always succeed as it can only
-        // interfere with `poll` in `Receiver` which is only called when the
-        // `complete` flag is true, which we're setting here.
-        let mut slot = self.inner.data.try_lock().unwrap();
+        drop(self.send(t));
+    }
+
+    /// Completes this oneshot with a successful result, handing the value
+    /// back if it can't be delivered.
+    ///
+    /// This function will consume `self` and indicate to the other end, the
+    /// `Receiver`, that the value provided is the result of the computation
+    /// this represents.
+    ///
+    /// If the value is successfully enqueued for the remote end to receive
+    /// then `Ok(())` is returned. If the `Receiver` has been dropped or closed
+    /// then `Err(t)` is returned with the value that couldn't be delivered.
+    pub fn send(self, t: T) -> Result<(), T> {
+        if self.inner.complete.load(SeqCst) {
+            return Err(t)
+        }
+
+        // Store the data for the receiver. Acquiring the lock can only fail if
+        // the `Receiver` is reading from the slot after having been closed, in
+        // which case it won't see our value anyway.
+        let mut slot = match self.inner.data.try_lock() {
+            Some(slot) => slot,
+            None => return Err(t),
+        };
         assert!(slot.is_none());
         *slot = Some(t);
         drop(slot);
+
+        // If the `Receiver` went away while we were storing the data then it
+        // may never see it, so try to take it back. If the data is already
+        // gone, or the lock is taken, then the `Receiver` is reading it.
+        if self.inner.complete.load(SeqCst) {
+            if let Some(mut slot) = self.inner.data.try_lock() {
+                if let Some(t) = slot.take() {
+                    return Err(t)
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Tests to see whether this `Sender`'s corresponding `Receiver` has gone
+    /// away, either by being dropped or through `Receiver::close`.
+    ///
+    /// Unlike `poll_cancel`, this function does not require the context of a
+    /// task and doesn't schedule a notification for when the `Receiver` goes
+    /// away.
+    pub fn is_canceled(&self) -> bool {
+        self.inner.complete.load(SeqCst)
     }
 
     /// Polls this `Sender` half to detect whether the `Receiver` this has
@@ -244,6 +289,28 @@ impl<T> Receiver<T> {
             }
         }
     }
+
+    /// Attempts to receive a value outside of the context of a task.
+    ///
+    /// Unlike `poll`, this function does not schedule the current task to be
+    /// notified when a value is sent, so it can be called from anywhere.
+    ///
+    /// Returns `Ok(Some(t))` if a value has been sent, `Ok(None)` if the
+    /// `Sender` is still alive and may yet send one, and `Err(Canceled)` if
+    /// the `Sender` was dropped without sending a value, or the value was
+    /// already received.
+    pub fn try_recv(&mut self) -> Result<Option<T>, Canceled> {
+        if !self.inner.complete.load(SeqCst) {
+            return Ok(None)
+        }
+
+        // The lock may be held by a `Sender` taking its value back after this
+        // receiver was closed, in which case there's nothing for us.
+        match self.inner.data.try_lock().and_then(|mut slot| slot.take()) {
+            Some(data) => Ok(Some(data)),
+            None => Err(Canceled),
+        }
+    }
 }
 
 impl<T> Future for Receiver<T> {
@@ -278,8 +345,12 @@ impl<T> Future for Receiver<T> {
         //
         // If we're not done, and we're not complete, though, then we've
         // successfully blocked our task and we return `NotReady`.
+        //
+        // Acquiring the `data` lock can only fail if we've been closed and
+        // the `Sender` is concurrently storing its value, which it'll then
+        // take back, so that's a cancellation as well.
         if done || self.inner.complete.load(SeqCst) {
-            match self.inner.data.try_lock().unwrap().take() {
+            match self.inner.data.try_lock().and_then(|mut slot| slot.take()) {
                 Some(data) => Ok(data.into()),
                 None => Err(Canceled),
             }
@@ -0,0 +1,415 @@
+use std::prelude::v1::*;
+
+use std::cell::UnsafeCell;
+use std::collections::{HashSet, VecDeque};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::{self, Arc};
+
+use {Async, Future, Poll};
+use task::{self, Task};
+
+/// A futures-aware reader-writer lock, shared between any number of tasks.
+///
+/// This lock allows any number of readers or at most one writer to access the
+/// protected data at any point in time. Like `Mutex`, acquiring the lock never
+/// blocks the current thread; the `read` and `write` methods return futures
+/// which resolve to guards once the lock has been acquired, parking the
+/// current task in the meantime.
+///
+/// The lock prefers writers: once a writer is waiting, new readers queue up
+/// behind it rather than sharing the lock with the readers already holding it,
+/// and when the lock is released waiting writers are served before waiting
+/// readers. This means a steady stream of readers can't starve writers,
+/// although a steady stream of writers can starve readers.
+///
+/// An `RwLock` is a cheaply cloneable handle to the protected data, and the
+/// guards it hands out own a reference to the data as well.
+pub struct RwLock<T> {
+    inner: Arc<Inner<T>>,
+}
+
+struct Inner<T> {
+    state: sync::Mutex<State>,
+    data: UnsafeCell<T>,
+}
+
+struct State {
+    // Number of readers holding the lock, including those it has been handed
+    // to which have yet to pick it up.
+    readers: usize,
+
+    // Whether a writer holds the lock, or it has been handed to one.
+    writer: bool,
+
+    // Tasks waiting to acquire the lock, in order of arrival.
+    waiters: VecDeque<Waiter>,
+    waiting_writers: usize,
+
+    // Tickets of waiters the lock has been handed to.
+    granted: HashSet<usize>,
+
+    next_ticket: usize,
+}
+
+struct Waiter {
+    ticket: usize,
+    write: bool,
+    task: Task,
+}
+
+unsafe impl<T: Send + Sync> Send for Inner<T> {}
+unsafe impl<T: Send + Sync> Sync for Inner<T> {}
+
+impl<T> RwLock<T> {
+    /// Creates a new reader-writer lock in an unlocked state ready for use.
+    pub fn new(t: T) -> RwLock<T> {
+        RwLock {
+            inner: Arc::new(Inner {
+                state: sync::Mutex::new(State {
+                    readers: 0,
+                    writer: false,
+                    waiters: VecDeque::new(),
+                    waiting_writers: 0,
+                    granted: HashSet::new(),
+                    next_ticket: 0,
+                }),
+                data: UnsafeCell::new(t),
+            }),
+        }
+    }
+
+    /// Returns a future which will resolve to a guard with shared access to
+    /// the protected data once the lock has been acquired for reading.
+    ///
+    /// The returned future never resolves to an error. Dropping it before it
+    /// resolves gives up its place in the queue of waiters.
+    pub fn read(&self) -> RwLockRead<T> {
+        RwLockRead {
+            inner: Some(self.inner.clone()),
+            ticket: None,
+        }
+    }
+
+    /// Returns a future which will resolve to a guard with exclusive access to
+    /// the protected data once the lock has been acquired for writing.
+    ///
+    /// The returned future never resolves to an error. Dropping it before it
+    /// resolves gives up its place in the queue of waiters.
+    pub fn write(&self) -> RwLockWrite<T> {
+        RwLockWrite {
+            inner: Some(self.inner.clone()),
+            ticket: None,
+        }
+    }
+
+    /// Attempts to acquire this lock for reading immediately, without waiting.
+    ///
+    /// This fails if a writer holds the lock or is waiting for it. This method
+    /// does not require the context of a task.
+    pub fn try_read(&self) -> Option<RwLockReadGuard<T>> {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.can_read() {
+            state.readers += 1;
+            Some(RwLockReadGuard::new(self.inner.clone()))
+        } else {
+            None
+        }
+    }
+
+    /// Attempts to acquire this lock for writing immediately, without waiting.
+    ///
+    /// This fails if the lock is held at all, or any other task is waiting for
+    /// it. This method does not require the context of a task.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<T>> {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.can_write() {
+            state.writer = true;
+            Some(RwLockWriteGuard::new(self.inner.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Clone for RwLock<T> {
+    fn clone(&self) -> RwLock<T> {
+        RwLock { inner: self.inner.clone() }
+    }
+}
+
+impl State {
+    fn can_read(&self) -> bool {
+        !self.writer && self.waiting_writers == 0
+    }
+
+    fn can_write(&self) -> bool {
+        !self.writer && self.readers == 0 && self.waiters.is_empty()
+    }
+
+    // Hands the lock to as many waiters as possible, returning the tasks to
+    // wake up. Waiting writers take priority over waiting readers.
+    fn assign(&mut self) -> Vec<Task> {
+        let mut tasks = Vec::new();
+        if self.writer {
+            return tasks
+        }
+
+        if self.waiting_writers > 0 {
+            if self.readers == 0 {
+                let pos = self.waiters.iter().position(|w| w.write).unwrap();
+                let waiter = self.waiters.remove(pos).unwrap();
+                self.waiting_writers -= 1;
+                self.writer = true;
+                self.granted.insert(waiter.ticket);
+                tasks.push(waiter.task);
+            }
+        } else {
+            for waiter in self.waiters.drain(..) {
+                self.readers += 1;
+                self.granted.insert(waiter.ticket);
+                tasks.push(waiter.task);
+            }
+        }
+        tasks
+    }
+}
+
+impl<T> Inner<T> {
+    // Polls for the lock on behalf of a `RwLockRead` or `RwLockWrite` future,
+    // returning whether it was acquired.
+    fn poll_acquire(&self, ticket: &mut Option<usize>, write: bool) -> bool {
+        let me = task::park();
+        let mut state = self.state.lock().unwrap();
+        match *ticket {
+            None if !write && state.can_read() => {
+                state.readers += 1;
+                true
+            }
+            None if write && state.can_write() => {
+                state.writer = true;
+                true
+            }
+            None => {
+                let t = state.next_ticket;
+                state.next_ticket = state.next_ticket.wrapping_add(1);
+                state.waiters.push_back(Waiter {
+                    ticket: t,
+                    write: write,
+                    task: me,
+                });
+                if write {
+                    state.waiting_writers += 1;
+                }
+                *ticket = Some(t);
+                false
+            }
+            Some(t) if state.granted.remove(&t) => {
+                *ticket = None;
+                true
+            }
+            Some(t) => {
+                // Still waiting, make sure the most recent task is the one
+                // which gets woken up.
+                for waiter in state.waiters.iter_mut() {
+                    if waiter.ticket == t {
+                        waiter.task = me;
+                        break
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    // Gives up on acquiring the lock, releasing it if it was already handed
+    // to us.
+    fn cancel(&self, ticket: usize, write: bool) {
+        let tasks = {
+            let mut state = self.state.lock().unwrap();
+            if state.granted.remove(&ticket) {
+                if write {
+                    state.writer = false;
+                } else {
+                    state.readers -= 1;
+                }
+            } else {
+                state.waiters.retain(|w| w.ticket != ticket);
+                if write {
+                    state.waiting_writers -= 1;
+                }
+            }
+            state.assign()
+        };
+        for task in tasks {
+            task.unpark();
+        }
+    }
+
+    fn unlock(&self, write: bool) {
+        let tasks = {
+            let mut state = self.state.lock().unwrap();
+            if write {
+                debug_assert!(state.writer);
+                state.writer = false;
+            } else {
+                debug_assert!(state.readers > 0);
+                state.readers -= 1;
+            }
+            state.assign()
+        };
+
+        // Unpark outside of the lock, the tasks may run immediately elsewhere.
+        for task in tasks {
+            task.unpark();
+        }
+    }
+}
+
+/// Future returned by `RwLock::read` which resolves to a `RwLockReadGuard`
+/// once the lock has been acquired for reading.
+#[must_use = "futures do nothing unless polled"]
+pub struct RwLockRead<T> {
+    inner: Option<Arc<Inner<T>>>,
+
+    // Our place in line, assigned the first time we fail to get the lock.
+    ticket: Option<usize>,
+}
+
+impl<T> Future for RwLockRead<T> {
+    type Item = RwLockReadGuard<T>;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<RwLockReadGuard<T>, ()> {
+        let acquired = self.inner.as_ref()
+            .expect("cannot poll RwLockRead twice")
+            .poll_acquire(&mut self.ticket, false);
+        if acquired {
+            Ok(Async::Ready(RwLockReadGuard::new(self.inner.take().unwrap())))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+impl<T> Drop for RwLockRead<T> {
+    fn drop(&mut self) {
+        if let (Some(inner), Some(ticket)) = (self.inner.as_ref(), self.ticket) {
+            inner.cancel(ticket, false);
+        }
+    }
+}
+
+/// Future returned by `RwLock::write` which resolves to a `RwLockWriteGuard`
+/// once the lock has been acquired for writing.
+#[must_use = "futures do nothing unless polled"]
+pub struct RwLockWrite<T> {
+    inner: Option<Arc<Inner<T>>>,
+
+    // Our place in line, assigned the first time we fail to get the lock.
+    ticket: Option<usize>,
+}
+
+impl<T> Future for RwLockWrite<T> {
+    type Item = RwLockWriteGuard<T>;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<RwLockWriteGuard<T>, ()> {
+        let acquired = self.inner.as_ref()
+            .expect("cannot poll RwLockWrite twice")
+            .poll_acquire(&mut self.ticket, true);
+        if acquired {
+            Ok(Async::Ready(RwLockWriteGuard::new(self.inner.take().unwrap())))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+impl<T> Drop for RwLockWrite<T> {
+    fn drop(&mut self) {
+        if let (Some(inner), Some(ticket)) = (self.inner.as_ref(), self.ticket) {
+            inner.cancel(ticket, true);
+        }
+    }
+}
+
+/// An RAII guard with shared access to the data in a `RwLock`, returned by
+/// the `RwLockRead` future and `RwLock::try_read`.
+///
+/// This implements `Deref` to `T`. When dropped, the read lock is released,
+/// waking up a writer waiting for the lock if this was the last reader.
+pub struct RwLockReadGuard<T> {
+    inner: Arc<Inner<T>>,
+
+    // Opt out of the automatic `Send` and `Sync` impls, see `MutexGuard`.
+    _marker: PhantomData<*mut T>,
+}
+
+unsafe impl<T: Send + Sync> Send for RwLockReadGuard<T> {}
+unsafe impl<T: Send + Sync> Sync for RwLockReadGuard<T> {}
+
+impl<T> RwLockReadGuard<T> {
+    fn new(inner: Arc<Inner<T>>) -> RwLockReadGuard<T> {
+        RwLockReadGuard {
+            inner: inner,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for RwLockReadGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.inner.data.get() }
+    }
+}
+
+impl<T> Drop for RwLockReadGuard<T> {
+    fn drop(&mut self) {
+        self.inner.unlock(false);
+    }
+}
+
+/// An RAII guard with exclusive access to the data in a `RwLock`, returned by
+/// the `RwLockWrite` future and `RwLock::try_write`.
+///
+/// This implements `Deref` and `DerefMut` to `T`. When dropped, the write lock
+/// is released, waking up the next tasks waiting for the lock.
+pub struct RwLockWriteGuard<T> {
+    inner: Arc<Inner<T>>,
+
+    // Opt out of the automatic `Send` and `Sync` impls, see `MutexGuard`.
+    _marker: PhantomData<*mut T>,
+}
+
+unsafe impl<T: Send + Sync> Send for RwLockWriteGuard<T> {}
+unsafe impl<T: Send + Sync> Sync for RwLockWriteGuard<T> {}
+
+impl<T> RwLockWriteGuard<T> {
+    fn new(inner: Arc<Inner<T>>) -> RwLockWriteGuard<T> {
+        RwLockWriteGuard {
+            inner: inner,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for RwLockWriteGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.inner.data.get() }
+    }
+}
+
+impl<T> DerefMut for RwLockWriteGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.inner.data.get() }
+    }
+}
+
+impl<T> Drop for RwLockWriteGuard<T> {
+    fn drop(&mut self) {
+        self.inner.unlock(true);
+    }
+}
@@ -0,0 +1,281 @@
+use std::prelude::v1::*;
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use {Async, Future, Poll};
+use task::{self, Task};
+
+/// A futures-aware counting semaphore.
+///
+/// A semaphore holds a number of permits which tasks can acquire through the
+/// `acquire` method, and which are returned when the resulting
+/// `SemaphorePermit` is dropped. This is typically used to bound the amount
+/// of concurrent work in flight across any number of tasks, regardless of
+/// where that work comes from.
+///
+/// Tasks waiting for permits are served in the order in which they started
+/// waiting. A request for a large number of permits at the front of the queue
+/// holds up smaller requests behind it, so that it can't be starved.
+///
+/// A `Semaphore` is a cheaply cloneable handle, and all clones share the same
+/// set of permits.
+///
+/// # Examples
+///
+/// ```
+/// use futures::Future;
+/// use futures::sync::Semaphore;
+///
+/// let semaphore = Semaphore::new(2);
+/// let permit = semaphore.acquire(2).wait().unwrap();
+/// assert_eq!(semaphore.available_permits(), 0);
+/// assert!(semaphore.try_acquire(1).is_none());
+///
+/// drop(permit);
+/// assert_eq!(semaphore.available_permits(), 2);
+/// ```
+#[derive(Clone)]
+pub struct Semaphore {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    state: Mutex<State>,
+}
+
+struct State {
+    // Permits which are available to be acquired.
+    permits: usize,
+
+    // Tasks waiting for permits, in order of arrival.
+    waiters: VecDeque<Waiter>,
+
+    // Tickets of waiters who have been given their permits but haven't yet
+    // picked them up.
+    granted: HashSet<usize>,
+
+    next_ticket: usize,
+}
+
+struct Waiter {
+    ticket: usize,
+    permits: usize,
+    task: Task,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with the given number of permits.
+    pub fn new(permits: usize) -> Semaphore {
+        Semaphore {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State {
+                    permits: permits,
+                    waiters: VecDeque::new(),
+                    granted: HashSet::new(),
+                    next_ticket: 0,
+                }),
+            }),
+        }
+    }
+
+    /// Returns a future which resolves to a `SemaphorePermit` holding `n`
+    /// permits once they're available.
+    ///
+    /// The returned future never resolves to an error. Dropping it before it
+    /// resolves gives up its place in the queue of waiters. Note that if `n`
+    /// is larger than the number of permits the semaphore will ever hold then
+    /// the future will never resolve.
+    pub fn acquire(&self, n: usize) -> SemaphoreAcquire {
+        SemaphoreAcquire {
+            inner: Some(self.inner.clone()),
+            permits: n,
+            ticket: None,
+        }
+    }
+
+    /// Attempts to acquire `n` permits immediately, without waiting.
+    ///
+    /// Permits are only handed out if enough are available and no other task
+    /// is already waiting for some. This method does not require the context
+    /// of a task.
+    pub fn try_acquire(&self, n: usize) -> Option<SemaphorePermit> {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.waiters.is_empty() && state.permits >= n {
+            state.permits -= n;
+            Some(SemaphorePermit {
+                inner: self.inner.clone(),
+                permits: n,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of permits which are currently available.
+    pub fn available_permits(&self) -> usize {
+        self.inner.state.lock().unwrap().permits
+    }
+
+    /// Adds `n` new permits to the semaphore, waking up any tasks which can
+    /// now acquire theirs.
+    pub fn add_permits(&self, n: usize) {
+        self.inner.release(n);
+    }
+}
+
+impl Inner {
+    fn release(&self, n: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.permits += n;
+        let tasks = state.assign();
+        drop(state);
+
+        // Unpark outside of the lock, the tasks may run immediately elsewhere.
+        for task in tasks {
+            task.unpark();
+        }
+    }
+}
+
+impl State {
+    // Hands out permits to the waiters at the front of the queue for as long
+    // as there are enough, returning the tasks to wake up.
+    fn assign(&mut self) -> Vec<Task> {
+        let mut tasks = Vec::new();
+        loop {
+            match self.waiters.front() {
+                Some(waiter) if waiter.permits <= self.permits => {}
+                _ => break,
+            }
+            let waiter = self.waiters.pop_front().unwrap();
+            self.permits -= waiter.permits;
+            self.granted.insert(waiter.ticket);
+            tasks.push(waiter.task);
+        }
+        tasks
+    }
+}
+
+/// Future returned by `Semaphore::acquire` which resolves to a
+/// `SemaphorePermit` once the requested permits have been acquired.
+#[must_use = "futures do nothing unless polled"]
+pub struct SemaphoreAcquire {
+    inner: Option<Arc<Inner>>,
+    permits: usize,
+
+    // Our place in line, assigned the first time we fail to get our permits.
+    ticket: Option<usize>,
+}
+
+impl Future for SemaphoreAcquire {
+    type Item = SemaphorePermit;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<SemaphorePermit, ()> {
+        let me = task::park();
+        let acquired = {
+            let inner = self.inner.as_ref().expect("cannot poll SemaphoreAcquire twice");
+            let mut state = inner.state.lock().unwrap();
+            match self.ticket {
+                None if state.waiters.is_empty() && state.permits >= self.permits => {
+                    state.permits -= self.permits;
+                    true
+                }
+                None => {
+                    let ticket = state.next_ticket;
+                    state.next_ticket = state.next_ticket.wrapping_add(1);
+                    state.waiters.push_back(Waiter {
+                        ticket: ticket,
+                        permits: self.permits,
+                        task: me,
+                    });
+                    self.ticket = Some(ticket);
+                    false
+                }
+                Some(ticket) if state.granted.remove(&ticket) => true,
+                Some(ticket) => {
+                    // Still waiting, make sure the most recent task is the
+                    // one which gets woken up.
+                    for waiter in state.waiters.iter_mut() {
+                        if waiter.ticket == ticket {
+                            waiter.task = me;
+                            break
+                        }
+                    }
+                    false
+                }
+            }
+        };
+
+        if acquired {
+            self.ticket = None;
+            Ok(Async::Ready(SemaphorePermit {
+                inner: self.inner.take().unwrap(),
+                permits: self.permits,
+            }))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
+
+impl Drop for SemaphoreAcquire {
+    fn drop(&mut self) {
+        let ticket = match self.ticket {
+            Some(ticket) => ticket,
+            None => return,
+        };
+        let inner = match self.inner {
+            Some(ref inner) => inner,
+            None => return,
+        };
+
+        let mut state = inner.state.lock().unwrap();
+        if state.granted.remove(&ticket) {
+            // We were given our permits but never picked them up, so give
+            // them back.
+            drop(state);
+            inner.release(self.permits);
+        } else {
+            // Leaving the queue may unblock the waiters behind us.
+            state.waiters.retain(|w| w.ticket != ticket);
+            let tasks = state.assign();
+            drop(state);
+            for task in tasks {
+                task.unpark();
+            }
+        }
+    }
+}
+
+/// An RAII guard representing permits acquired from a `Semaphore`.
+///
+/// The permits are returned to the semaphore when this is dropped, waking up
+/// any tasks waiting for them.
+pub struct SemaphorePermit {
+    inner: Arc<Inner>,
+    permits: usize,
+}
+
+impl SemaphorePermit {
+    /// Returns the number of permits held by this guard.
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Forgets this guard without returning its permits to the semaphore,
+    /// permanently reducing the number of permits available.
+    pub fn forget(mut self) {
+        self.permits = 0;
+        drop(self);
+    }
+}
+
+impl Drop for SemaphorePermit {
+    fn drop(&mut self) {
+        if self.permits > 0 {
+            self.inner.release(self.permits);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! A single-producer, multi-consumer, futures-aware channel which only
+//! retains the most recently sent value.
+//!
+//! This is useful for propagating state, such as configuration, where
+//! consumers only care about the latest version rather than every
+//! intermediate one. The `Sender` overwrites a single slot, and each
+//! `Receiver` implements `Stream`, yielding every version of the value it
+//! hasn't yet seen exactly once. If several values are sent in between two
+//! polls of a receiver then it only sees the last of them.
+//!
+//! The current value can also be inspected at any time, without waiting,
+//! through `Receiver::borrow`.
+//!
+//! When the `Sender` is dropped, receivers yield the final value if they
+//! haven't seen it yet and then terminate.
+
+use std::prelude::v1::*;
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use std::sync::atomic::{AtomicBool, AtomicUsize};
+use std::sync::atomic::Ordering::SeqCst;
+
+use {Async, Poll, Stream};
+use lock::Lock;
+use task::{self, Task};
+
+/// The sending half of a watch channel, used to update the value.
+///
+/// This is created by the `channel` function.
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+/// The receiving half of a watch channel which implements the `Stream` trait.
+///
+/// This is created by the `channel` function, or by cloning another
+/// `Receiver`. A clone starts out having seen the same versions as the
+/// receiver it was cloned from.
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+
+    // The version of the value this receiver last yielded.
+    version: usize,
+
+    // Slot holding the task blocked in `poll`, shared with the `Sender` so it
+    // can wake us up. This follows the same protocol as `rx_task` in
+    // `oneshot`.
+    task: Arc<Lock<Option<Task>>>,
+    id: usize,
+}
+
+/// Error type for sending, used when all receivers have been dropped.
+pub struct SendError<T>(T);
+
+/// A reference to the current value of a watch channel, returned by
+/// `Receiver::borrow`.
+///
+/// Outstanding references block the `Sender` from updating the value, so they
+/// shouldn't be held for long.
+pub struct Ref<'a, T: 'a> {
+    guard: RwLockReadGuard<'a, T>,
+}
+
+struct Inner<T> {
+    value: RwLock<T>,
+
+    // Incremented every time a new value is stored, while holding the write
+    // lock on `value`.
+    version: AtomicUsize,
+
+    // Set once the `Sender` has been dropped.
+    closed: AtomicBool,
+
+    // The task slots of all live receivers, keyed by receiver id.
+    rx_tasks: Mutex<HashMap<usize, Arc<Lock<Option<Task>>>>>,
+    next_id: AtomicUsize,
+}
+
+/// Creates a new watch channel holding `init` as its initial value.
+///
+/// The returned receiver considers `init` already seen, so it's only yielded
+/// from the stream once it's been replaced, but it's available right away
+/// through `Receiver::borrow`.
+///
+/// # Examples
+///
+/// ```
+/// use futures::{Future, Stream};
+/// use futures::sync::watch;
+///
+/// let (mut tx, rx) = watch::channel("initial");
+/// assert_eq!(*rx.borrow(), "initial");
+///
+/// tx.send("first").unwrap();
+/// tx.send("second").unwrap();
+/// drop(tx);
+///
+/// // Only the latest value is seen.
+/// assert_eq!(rx.collect().wait(), Ok(vec!["second"]));
+/// ```
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Inner {
+        value: RwLock::new(init),
+        version: AtomicUsize::new(0),
+        closed: AtomicBool::new(false),
+        rx_tasks: Mutex::new(HashMap::new()),
+        next_id: AtomicUsize::new(0),
+    });
+    let receiver = Receiver::new(inner.clone(), 0);
+    let sender = Sender {
+        inner: inner,
+    };
+    (sender, receiver)
+}
+
+impl<T> Sender<T> {
+    /// Replaces the value in the channel, waking up all receivers.
+    ///
+    /// An error is returned, handing back the value, if all receivers have
+    /// been dropped.
+    pub fn send(&mut self, value: T) -> Result<(), SendError<T>> {
+        if self.inner.rx_tasks.lock().unwrap().is_empty() {
+            return Err(SendError(value))
+        }
+
+        {
+            let mut slot = self.inner.value.write().unwrap();
+            *slot = value;
+            self.inner.version.fetch_add(1, SeqCst);
+        }
+        self.inner.unpark_all();
+        Ok(())
+    }
+
+    /// Returns a reference to the current value.
+    pub fn borrow(&self) -> Ref<T> {
+        Ref { guard: self.inner.value.read().unwrap() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.inner.closed.store(true, SeqCst);
+        self.inner.unpark_all();
+    }
+}
+
+impl<T> Inner<T> {
+    fn unpark_all(&self) {
+        let slots = self.rx_tasks.lock().unwrap().values().cloned().collect::<Vec<_>>();
+
+        // If a lock acquisition fails here it's because the receiver is in
+        // the middle of storing its task, in which case it'll check the
+        // version again afterwards and see our update.
+        for slot in slots {
+            if let Some(mut slot) = slot.try_lock() {
+                if let Some(task) = slot.take() {
+                    drop(slot);
+                    task.unpark();
+                }
+            }
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    fn new(inner: Arc<Inner<T>>, version: usize) -> Receiver<T> {
+        let id = inner.next_id.fetch_add(1, SeqCst);
+        let task = Arc::new(Lock::new(None));
+        inner.rx_tasks.lock().unwrap().insert(id, task.clone());
+        Receiver {
+            inner: inner,
+            version: version,
+            task: task,
+            id: id,
+        }
+    }
+
+    /// Returns a reference to the current value, without waiting and without
+    /// marking it as seen.
+    ///
+    /// Outstanding references block the `Sender` from updating the value, so
+    /// they shouldn't be held for long.
+    pub fn borrow(&self) -> Ref<T> {
+        Ref { guard: self.inner.value.read().unwrap() }
+    }
+}
+
+impl<T: Clone> Receiver<T> {
+    // Returns the current value if it's a version we haven't seen yet, or
+    // `None` if the channel is closed and we've seen the final value.
+    fn poll_value(&mut self) -> Option<Async<Option<T>>> {
+        let value = self.inner.value.read().unwrap();
+        let version = self.inner.version.load(SeqCst);
+        if version != self.version {
+            self.version = version;
+            Some(Async::Ready(Some(value.clone())))
+        } else if self.inner.closed.load(SeqCst) {
+            Some(Async::Ready(None))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = T;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<T>, ()> {
+        if let Some(ready) = self.poll_value() {
+            return Ok(ready)
+        }
+
+        // Block our task, and then check again in case a new value was sent
+        // in the meantime. If the lock acquisition fails then the `Sender` is
+        // trying to wake us up, which means there's something new to see.
+        let task = task::park();
+        if let Some(mut slot) = self.task.try_lock() {
+            *slot = Some(task);
+        }
+        Ok(self.poll_value().unwrap_or(Async::NotReady))
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        Receiver::new(self.inner.clone(), self.version)
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.inner.rx_tasks.lock().unwrap().remove(&self.id);
+    }
+}
+
+impl<'a, T> Deref for Ref<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("SendError")
+            .field(&"...")
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "send failed because all receivers are gone")
+    }
+}
+
+impl<T> Error for SendError<T>
+    where T: Any
+{
+    fn description(&self) -> &str {
+        "send failed because all receivers are gone"
+    }
+}
+
+impl<T> SendError<T> {
+    /// Returns the value that was attempted to be sent but failed.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use {Async, Future, Never, Poll};
+use timer::Timer;
+use timer::wheel::{Entry, Key};
+
+/// A future which completes at a specific point in time.
+///
+/// This is created by the `Timer::delay` and `Timer::delay_until` methods, or
+/// the `timer::delay` and `timer::delay_until` functions.
+#[must_use = "futures do nothing unless polled"]
+pub struct Delay {
+    timer: Timer,
+    deadline: Instant,
+    entry: Arc<Entry>,
+
+    // Our key in the timer's wheel, while we're registered with it.
+    key: Option<Key>,
+}
+
+pub fn new(timer: Timer, deadline: Instant) -> Delay {
+    let (entry, key) = timer.register(deadline);
+    Delay {
+        timer: timer,
+        deadline: deadline,
+        entry: entry,
+        key: key,
+    }
+}
+
+impl Delay {
+    /// Returns the point in time at which this future completes.
+    ///
+    /// This isn't called `deadline`, as that would be shadowed by
+    /// `Future::deadline` when called on an owned `Delay`.
+    pub fn expires_at(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Resets this future to complete at `deadline` instead, even if it has
+    /// already completed.
+    pub fn reset(&mut self, deadline: Instant) {
+        self.deregister();
+        let (entry, key) = self.timer.register(deadline);
+        self.deadline = deadline;
+        self.entry = entry;
+        self.key = key;
+    }
+
+    fn is_elapsed(&self) -> bool {
+        // The clock may already have passed the deadline before the timer
+        // has gotten around to firing our entry.
+        self.entry.is_fired() || self.timer.now() >= self.deadline
+    }
+
+    fn deregister(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.timer.deregister(key);
+        }
+    }
+}
+
+impl Future for Delay {
+    type Item = ();
+    type Error = Never;
+
+    fn poll(&mut self) -> Poll<(), Never> {
+        if !self.is_elapsed() {
+            // Register and then check again, so firing in between isn't
+            // missed.
+            self.entry.register();
+            if !self.entry.is_fired() {
+                return Ok(Async::NotReady)
+            }
+        }
+
+        self.deregister();
+        Ok(Async::Ready(()))
+    }
+}
+
+impl Drop for Delay {
+    fn drop(&mut self) {
+        self.deregister();
+    }
+}
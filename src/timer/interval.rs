@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use {Async, Future, Never, Poll};
+use stream::Stream;
+use timer::{Delay, Timer};
+
+/// A stream which yields an item at a fixed period.
+///
+/// This is created by the `Timer::interval` method, or the `timer::interval`
+/// function.
+///
+/// Ticks are scheduled relative to the previous deadline rather than to when
+/// the previous item was yielded, so the stream doesn't drift. If the stream
+/// isn't polled for longer than a period, the missed items are yielded
+/// immediately on the following polls.
+#[must_use = "streams do nothing unless polled"]
+pub struct Interval {
+    delay: Delay,
+    period: Duration,
+}
+
+pub fn new(timer: Timer, period: Duration) -> Interval {
+    assert!(period > Duration::from_secs(0), "interval period must be non-zero");
+    Interval {
+        delay: timer.delay(period),
+        period: period,
+    }
+}
+
+impl Interval {
+    /// Returns the period of this interval.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+}
+
+impl Stream for Interval {
+    type Item = ();
+    type Error = Never;
+
+    fn poll(&mut self) -> Poll<Option<()>, Never> {
+        try_ready!(self.delay.poll());
+        let next = self.delay.expires_at() + self.period;
+        self.delay.reset(next);
+        Ok(Async::Ready(Some(())))
+    }
+}
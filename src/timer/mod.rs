@@ -0,0 +1,439 @@
+//! Timers
+//!
+//! This module contains the `Delay` future and the `Interval` stream, which
+//! complete at a point in time, along with the `Timer` they're registered
+//! with. Timers also back the `Timeout` future and `TimeoutStream`, created
+//! by `Future::timeout`, `Future::deadline` and `Stream::timeout`.
+//!
+//! A `Timer` keeps track of timeouts in a hashed timer wheel, which is driven
+//! by a background thread. The free functions in this module, such as
+//! `delay` and `interval`, use a default timer which is started the first
+//! time it's needed. A timer can also be created with a `MockClock`, in which case no
+//! background thread is started and time only moves forward when the clock
+//! is advanced, so code depending on time can be tested deterministically.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use futures::Future;
+//! use futures::timer::Timer;
+//!
+//! let (timer, clock) = Timer::mock();
+//! let delay = timer.delay(Duration::from_secs(60));
+//!
+//! // Advancing the clock fires the delay right away, no need to wait for a
+//! // minute.
+//! clock.advance(Duration::from_secs(60));
+//! delay.wait().unwrap();
+//! ```
+
+use std::prelude::v1::*;
+
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::atomic::{AtomicUsize, ATOMIC_USIZE_INIT};
+use std::sync::atomic::Ordering::SeqCst;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use {Future, Stream};
+
+mod delay;
+mod interval;
+mod timeout;
+mod wheel;
+
+pub use self::delay::Delay;
+pub use self::interval::Interval;
+pub use self::timeout::{Timeout, TimeoutStream, TimeoutError};
+
+use self::wheel::{Entry, Key, Wheel};
+
+// The resolution of timers, and the number of slots in their wheel.
+const TICK_NANOS: u64 = 1_000_000;
+const NUM_SLOTS: usize = 512;
+
+/// A handle to a timer, which `Delay` futures and `Interval` streams are
+/// registered with.
+///
+/// A `Timer` is a cheaply cloneable handle, and all clones refer to the same
+/// timer. A timer created with `Timer::new` runs a background thread, which
+/// shuts down once all handles to the timer, including the `Delay`s and
+/// `Interval`s created from it, have been dropped.
+#[derive(Clone)]
+pub struct Timer {
+    handle: Arc<Handle>,
+}
+
+/// A handle to the clock of a timer created with `Timer::mock`, which is used
+/// to move time forward.
+#[derive(Clone)]
+pub struct MockClock {
+    inner: Arc<Inner>,
+}
+
+// Shuts the timer down once the last handle goes away.
+struct Handle {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    state: Mutex<State>,
+
+    // Used to wake up the background thread, if there is one.
+    condvar: Condvar,
+
+    // The point in time corresponding to the wheel's tick 0.
+    origin: Instant,
+
+    mock: bool,
+}
+
+struct State {
+    wheel: Wheel,
+
+    // For mock clocks, the time elapsed since `origin`.
+    elapsed: Duration,
+
+    // The tick the background thread is sleeping until, or `None` if it's
+    // waiting for a timeout to be registered.
+    wakeup: Option<u64>,
+
+    shutdown: bool,
+}
+
+impl Timer {
+    /// Creates a new timer, which is driven by a background thread using the
+    /// system clock.
+    pub fn new() -> Timer {
+        let inner = Inner::new(false);
+        let thread_inner = inner.clone();
+        thread::Builder::new()
+            .name("futures-timer".to_string())
+            .spawn(move || run(&thread_inner))
+            .expect("failed to spawn timer thread");
+        Timer {
+            handle: Arc::new(Handle { inner: inner }),
+        }
+    }
+
+    /// Creates a new timer using a mock clock, along with the handle used to
+    /// advance it.
+    ///
+    /// No background thread is started for the returned timer. Instead, the
+    /// clock only moves forward when `MockClock::advance` is called, which
+    /// also fires all the timeouts which have expired.
+    pub fn mock() -> (Timer, MockClock) {
+        let inner = Inner::new(true);
+        let clock = MockClock { inner: inner.clone() };
+        (Timer { handle: Arc::new(Handle { inner: inner }) }, clock)
+    }
+
+    /// Returns the current time according to this timer's clock.
+    pub fn now(&self) -> Instant {
+        self.inner().now()
+    }
+
+    /// Creates a future which completes once `duration` has elapsed.
+    pub fn delay(&self, duration: Duration) -> Delay {
+        let deadline = self.now() + duration;
+        self.delay_until(deadline)
+    }
+
+    /// Creates a future which completes at `deadline`.
+    ///
+    /// If the deadline has already passed then the future completes
+    /// immediately.
+    pub fn delay_until(&self, deadline: Instant) -> Delay {
+        delay::new(self.clone(), deadline)
+    }
+
+    /// Creates a stream which yields an item every `period`, starting one
+    /// period from now.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `period` is zero.
+    pub fn interval(&self, period: Duration) -> Interval {
+        interval::new(self.clone(), period)
+    }
+
+    /// Wraps `future` so that it fails with `TimeoutError::Elapsed` unless
+    /// it completes within `duration`.
+    ///
+    /// See `Future::timeout` for more details.
+    pub fn timeout<F: Future>(&self, future: F, duration: Duration) -> Timeout<F> {
+        timeout::new(future, self.delay(duration))
+    }
+
+    /// Wraps `future` so that it fails with `TimeoutError::Elapsed` unless
+    /// it completes before `deadline`.
+    ///
+    /// See `Future::deadline` for more details.
+    pub fn deadline<F: Future>(&self, future: F, deadline: Instant) -> Timeout<F> {
+        timeout::new(future, self.delay_until(deadline))
+    }
+
+    /// Wraps `stream` so that it yields a `TimeoutError::Elapsed` error
+    /// whenever it goes `duration` without producing an item.
+    ///
+    /// See `Stream::timeout` for more details.
+    pub fn timeout_stream<S: Stream>(&self, stream: S, duration: Duration) -> TimeoutStream<S> {
+        timeout::new_stream(stream, self.clone(), duration)
+    }
+
+    fn inner(&self) -> &Inner {
+        &self.handle.inner
+    }
+
+    // Registers a timeout at `deadline`, returning `None` as the key if it
+    // has already expired.
+    fn register(&self, deadline: Instant) -> (Arc<Entry>, Option<Key>) {
+        let inner = self.inner();
+        let when = inner.tick_of(deadline);
+        let mut state = inner.state.lock().unwrap();
+        let entry = Entry::new(false);
+        match state.wheel.insert(when, entry.clone()) {
+            Some(key) => {
+                // The background thread sleeps until the next timeout is
+                // due, so it only needs waking if this one comes first.
+                let earlier = match state.wakeup {
+                    Some(wakeup) => when < wakeup,
+                    None => true,
+                };
+                if earlier {
+                    inner.condvar.notify_one();
+                }
+                (entry, Some(key))
+            }
+            None => (Entry::new(true), None),
+        }
+    }
+
+    fn deregister(&self, key: Key) {
+        self.inner().state.lock().unwrap().wheel.remove(key);
+    }
+}
+
+impl Default for Timer {
+    fn default() -> Timer {
+        Timer::new()
+    }
+}
+
+impl fmt::Debug for Timer {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Timer")
+            .field("mock", &self.inner().mock)
+            .finish()
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        self.inner.state.lock().unwrap().shutdown = true;
+        self.inner.condvar.notify_one();
+    }
+}
+
+impl MockClock {
+    /// Returns the current time according to this clock.
+    pub fn now(&self) -> Instant {
+        self.inner.now()
+    }
+
+    /// Moves this clock forward by `duration`, firing all the timeouts which
+    /// have expired in the meantime.
+    ///
+    /// The tasks waiting on those timeouts are woken up before this function
+    /// returns.
+    pub fn advance(&self, duration: Duration) {
+        let expired = {
+            let mut state = self.inner.state.lock().unwrap();
+            state.elapsed += duration;
+            let now = self.inner.origin + state.elapsed;
+            let tick = self.inner.current_tick(now);
+            state.wheel.advance(tick)
+        };
+        for entry in expired {
+            entry.fire();
+        }
+    }
+}
+
+impl fmt::Debug for MockClock {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("MockClock")
+            .field("now", &self.now())
+            .finish()
+    }
+}
+
+impl Inner {
+    fn new(mock: bool) -> Arc<Inner> {
+        Arc::new(Inner {
+            state: Mutex::new(State {
+                wheel: Wheel::new(NUM_SLOTS),
+                elapsed: Duration::from_secs(0),
+                wakeup: None,
+                shutdown: false,
+            }),
+            condvar: Condvar::new(),
+            origin: Instant::now(),
+            mock: mock,
+        })
+    }
+
+    fn now(&self) -> Instant {
+        if self.mock {
+            self.origin + self.state.lock().unwrap().elapsed
+        } else {
+            Instant::now()
+        }
+    }
+
+    fn nanos_since_origin(&self, at: Instant) -> u64 {
+        if at <= self.origin {
+            return 0
+        }
+        let d = at - self.origin;
+        d.as_secs() * 1_000_000_000 + d.subsec_nanos() as u64
+    }
+
+    // The tick at which a timeout at `deadline` expires, rounding up so that
+    // timeouts never fire early.
+    fn tick_of(&self, deadline: Instant) -> u64 {
+        (self.nanos_since_origin(deadline) + TICK_NANOS - 1) / TICK_NANOS
+    }
+
+    // The point in time at which `tick` is reached.
+    fn instant_of(&self, tick: u64) -> Instant {
+        let nanos = tick * TICK_NANOS;
+        self.origin + Duration::new(nanos / 1_000_000_000,
+                                    (nanos % 1_000_000_000) as u32)
+    }
+
+    // The last tick which has been fully reached at `now`.
+    fn current_tick(&self, now: Instant) -> u64 {
+        self.nanos_since_origin(now) / TICK_NANOS
+    }
+}
+
+// The main loop of the background thread driving a timer.
+fn run(inner: &Inner) {
+    let mut state = inner.state.lock().unwrap();
+    loop {
+        if state.shutdown {
+            return
+        }
+
+        let expired = state.wheel.advance(inner.current_tick(Instant::now()));
+        if !expired.is_empty() {
+            // Fire the expired timeouts outside of the lock, as the tasks
+            // may run immediately elsewhere and register new ones.
+            drop(state);
+            for entry in expired {
+                entry.fire();
+            }
+            state = inner.state.lock().unwrap();
+            continue
+        }
+
+        // Sleep until the next timeout is due. Registering an earlier one
+        // wakes us up sooner.
+        state.wakeup = state.wheel.next_expiration();
+        state = match state.wakeup {
+            Some(when) => {
+                let deadline = inner.instant_of(when);
+                let now = Instant::now();
+                if deadline <= now {
+                    continue
+                }
+                inner.condvar.wait_timeout(state, deadline - now).unwrap().0
+            }
+            None => inner.condvar.wait(state).unwrap(),
+        };
+    }
+}
+
+// Returns a handle to the default timer used by the free functions in this
+// module, which is driven by a background thread started the first time it's
+// needed.
+fn default_timer() -> Timer {
+    // A pointer to the leaked `Timer`, or 0 if it hasn't been created.
+    static DEFAULT: AtomicUsize = ATOMIC_USIZE_INIT;
+
+    let mut timer = DEFAULT.load(SeqCst) as *mut Timer;
+    if timer.is_null() {
+        let new = Box::into_raw(Box::new(Timer::new()));
+        match DEFAULT.compare_exchange(0, new as usize, SeqCst, SeqCst) {
+            Ok(_) => timer = new,
+            Err(other) => {
+                // Somebody else got there first, so shut ours down again.
+                drop(unsafe { Box::from_raw(new) });
+                timer = other as *mut Timer;
+            }
+        }
+    }
+
+    // The default timer is never freed.
+    unsafe { (*timer).clone() }
+}
+
+/// Creates a future which completes once `duration` has elapsed, using the
+/// default timer.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use futures::Future;
+/// use futures::timer;
+///
+/// let start = Instant::now();
+/// timer::delay(Duration::from_millis(10)).wait().unwrap();
+/// assert!(start.elapsed() >= Duration::from_millis(10));
+/// ```
+pub fn delay(duration: Duration) -> Delay {
+    default_timer().delay(duration)
+}
+
+/// Creates a future which completes at `deadline`, using the default timer.
+pub fn delay_until(deadline: Instant) -> Delay {
+    default_timer().delay_until(deadline)
+}
+
+/// Creates a stream which yields an item every `period`, starting one period
+/// from now, using the default timer.
+///
+/// # Panics
+///
+/// This function will panic if `period` is zero.
+pub fn interval(period: Duration) -> Interval {
+    default_timer().interval(period)
+}
+
+/// Wraps `future` so that it fails with `TimeoutError::Elapsed` unless it
+/// completes within `duration`, using the default timer.
+///
+/// This is what `Future::timeout` uses.
+pub fn timeout<F: Future>(future: F, duration: Duration) -> Timeout<F> {
+    default_timer().timeout(future, duration)
+}
+
+/// Wraps `future` so that it fails with `TimeoutError::Elapsed` unless it
+/// completes before `deadline`, using the default timer.
+///
+/// This is what `Future::deadline` uses.
+pub fn deadline<F: Future>(future: F, deadline: Instant) -> Timeout<F> {
+    default_timer().deadline(future, deadline)
+}
+
+/// Wraps `stream` so that it yields a `TimeoutError::Elapsed` error whenever
+/// it goes `duration` without producing an item, using the default timer.
+///
+/// This is what `Stream::timeout` uses.
+pub fn timeout_stream<S: Stream>(stream: S, duration: Duration) -> TimeoutStream<S> {
+    default_timer().timeout_stream(stream, duration)
+}
@@ -0,0 +1,189 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use {Async, Future, Poll};
+use stream::Stream;
+use timer::{Delay, Timer};
+
+/// Future for the `timeout` and `deadline` combinators, which fails with
+/// `TimeoutError::Elapsed` if the wrapped future doesn't complete in time.
+///
+/// This is created by the `Future::timeout` and `Future::deadline` methods,
+/// or the `Timer::timeout` and `Timer::deadline` methods.
+#[must_use = "futures do nothing unless polled"]
+pub struct Timeout<F> {
+    future: Option<F>,
+    delay: Delay,
+}
+
+/// Stream for the `timeout` combinator, which yields a
+/// `TimeoutError::Elapsed` error whenever the wrapped stream doesn't produce
+/// an item in time.
+///
+/// This is created by the `Stream::timeout` method, or the
+/// `Timer::timeout_stream` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct TimeoutStream<S> {
+    stream: S,
+    timer: Timer,
+    duration: Duration,
+    delay: Delay,
+}
+
+/// Error returned from a `Timeout` future or a `TimeoutStream`, which
+/// distinguishes the timeout elapsing from an error of the wrapped future or
+/// stream.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TimeoutError<E> {
+    /// The timeout elapsed before the wrapped future or stream was ready.
+    Elapsed,
+
+    /// The wrapped future or stream produced an error.
+    Inner(E),
+}
+
+impl<E> TimeoutError<E> {
+    /// Returns whether this error was caused by the timeout elapsing.
+    pub fn is_elapsed(&self) -> bool {
+        match *self {
+            TimeoutError::Elapsed => true,
+            TimeoutError::Inner(_) => false,
+        }
+    }
+
+    /// Returns the error of the wrapped future or stream, if that's what
+    /// this error is.
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            TimeoutError::Elapsed => None,
+            TimeoutError::Inner(e) => Some(e),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for TimeoutError<E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TimeoutError::Elapsed => write!(fmt, "timeout elapsed"),
+            TimeoutError::Inner(ref e) => e.fmt(fmt),
+        }
+    }
+}
+
+impl<E: Error> Error for TimeoutError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            TimeoutError::Elapsed => "timeout elapsed",
+            TimeoutError::Inner(_) => "wrapped future failed",
+        }
+    }
+}
+
+pub fn new<F: Future>(future: F, delay: Delay) -> Timeout<F> {
+    Timeout {
+        future: Some(future),
+        delay: delay,
+    }
+}
+
+pub fn new_stream<S: Stream>(stream: S, timer: Timer, duration: Duration) -> TimeoutStream<S> {
+    TimeoutStream {
+        stream: stream,
+        delay: timer.delay(duration),
+        timer: timer,
+        duration: duration,
+    }
+}
+
+impl<F> Timeout<F> {
+    /// Get a shared reference to the inner future, if it hasn't been dropped
+    /// because the timeout elapsed.
+    pub fn get_ref(&self) -> Option<&F> {
+        self.future.as_ref()
+    }
+
+    /// Get a mutable reference to the inner future, if it hasn't been
+    /// dropped because the timeout elapsed.
+    pub fn get_mut(&mut self) -> Option<&mut F> {
+        self.future.as_mut()
+    }
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Item = F::Item;
+    type Error = TimeoutError<F::Error>;
+
+    fn poll(&mut self) -> Poll<F::Item, TimeoutError<F::Error>> {
+        {
+            let future = self.future.as_mut()
+                .expect("cannot poll Timeout after the timeout has elapsed");
+            match future.poll() {
+                Ok(Async::Ready(e)) => return Ok(Async::Ready(e)),
+                Ok(Async::NotReady) => {}
+                Err(e) => return Err(TimeoutError::Inner(e)),
+            }
+        }
+
+        match self.delay.poll() {
+            Ok(Async::Ready(())) => {
+                self.future = None;
+                Err(TimeoutError::Elapsed)
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl<S> TimeoutStream<S> {
+    /// Get a shared reference to the inner stream.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Get a mutable reference to the inner stream.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn restart(&mut self) {
+        let deadline = self.timer.now() + self.duration;
+        self.delay.reset(deadline);
+    }
+}
+
+impl<S: Stream> Stream for TimeoutStream<S> {
+    type Item = S::Item;
+    type Error = TimeoutError<S::Error>;
+
+    fn poll(&mut self) -> Poll<Option<S::Item>, TimeoutError<S::Error>> {
+        match self.stream.poll() {
+            Ok(Async::Ready(e)) => {
+                self.restart();
+                return Ok(Async::Ready(e))
+            }
+            Ok(Async::NotReady) => {}
+            Err(e) => {
+                self.restart();
+                return Err(TimeoutError::Inner(e))
+            }
+        }
+
+        match self.delay.poll() {
+            Ok(Async::Ready(())) => {
+                // Keep going after reporting the timeout, it's up to the
+                // consumer whether an idle stream should be abandoned.
+                self.restart();
+                Err(TimeoutError::Elapsed)
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(never) => match never {},
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::prelude::v1::*;
+
+use std::cmp;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::SeqCst;
+
+use sync::AtomicTask;
+
+/// The state of a single timeout shared between the wheel and the future
+/// waiting on it.
+pub struct Entry {
+    fired: AtomicBool,
+    task: AtomicTask,
+}
+
+impl Entry {
+    pub fn new(fired: bool) -> Arc<Entry> {
+        Arc::new(Entry {
+            fired: AtomicBool::new(fired),
+            task: AtomicTask::new(),
+        })
+    }
+
+    pub fn is_fired(&self) -> bool {
+        self.fired.load(SeqCst)
+    }
+
+    pub fn register(&self) {
+        self.task.register();
+    }
+
+    pub fn fire(&self) {
+        self.fired.store(true, SeqCst);
+        self.task.notify();
+    }
+}
+
+/// Key of an entry in the wheel, used to remove it again.
+#[derive(Clone, Copy)]
+pub struct Key {
+    slot: usize,
+    id: usize,
+}
+
+/// A hashed timer wheel.
+///
+/// Time is divided into ticks, and each entry is stored in the slot of the
+/// tick it expires at, modulo the number of slots. Advancing the wheel by a
+/// tick only needs to look at a single slot, skipping the entries which are
+/// due in a later rotation of the wheel.
+pub struct Wheel {
+    slots: Vec<HashMap<usize, (u64, Arc<Entry>)>>,
+
+    // The last tick which has been processed, all entries expiring at or
+    // before it have been fired.
+    tick: u64,
+
+    next_id: usize,
+    len: usize,
+}
+
+impl Wheel {
+    pub fn new(num_slots: usize) -> Wheel {
+        assert!(num_slots > 0);
+        Wheel {
+            slots: (0..num_slots).map(|_| HashMap::new()).collect(),
+            tick: 0,
+            next_id: 0,
+            len: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts an entry expiring at tick `when`.
+    ///
+    /// Returns `None` without inserting the entry if that tick has already
+    /// been processed, in which case the caller should consider the entry
+    /// expired.
+    pub fn insert(&mut self, when: u64, entry: Arc<Entry>) -> Option<Key> {
+        if when <= self.tick {
+            return None
+        }
+
+        let slot = (when % self.slots.len() as u64) as usize;
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.slots[slot].insert(id, (when, entry));
+        self.len += 1;
+        Some(Key { slot: slot, id: id })
+    }
+
+    /// Removes an entry which hasn't expired yet.
+    pub fn remove(&mut self, key: Key) {
+        if self.slots[key.slot].remove(&key.id).is_some() {
+            self.len -= 1;
+        }
+    }
+
+    /// Returns the tick at which the next entry expires, if there are any.
+    pub fn next_expiration(&self) -> Option<u64> {
+        if self.is_empty() {
+            return None
+        }
+
+        // Visiting the slots in order finds the entries expiring within the
+        // next rotation, as the earliest tick a slot can hold is the one
+        // it's visited at.
+        let num_slots = self.slots.len() as u64;
+        for tick in self.tick + 1..self.tick + 1 + num_slots {
+            let slot = &self.slots[(tick % num_slots) as usize];
+            if slot.values().any(|&(when, _)| when == tick) {
+                return Some(tick)
+            }
+        }
+
+        // Everything is at least a rotation away.
+        self.slots.iter()
+                  .flat_map(|slot| slot.values())
+                  .map(|&(when, _)| when)
+                  .min()
+    }
+
+    /// Advances the wheel up to and including tick `now`, returning the
+    /// entries which have expired.
+    pub fn advance(&mut self, now: u64) -> Vec<Arc<Entry>> {
+        let mut expired = Vec::new();
+        if now <= self.tick {
+            return expired
+        }
+
+        // Once we've gone around the whole wheel every slot has been looked
+        // at, so there's no need to visit them again.
+        let num_slots = self.slots.len() as u64;
+        let ticks = cmp::min(now - self.tick, num_slots);
+        for tick in self.tick + 1..self.tick + 1 + ticks {
+            let slot = &mut self.slots[(tick % num_slots) as usize];
+            let ids = slot.iter()
+                          .filter(|&(_, &(when, _))| when <= now)
+                          .map(|(&id, _)| id)
+                          .collect::<Vec<_>>();
+            for id in ids {
+                expired.push(slot.remove(&id).unwrap().1);
+            }
+        }
+        self.len -= expired.len();
+        self.tick = now;
+        expired
+    }
+}
@@ -0,0 +1,103 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Future, Async};
+use futures::executor::{self, CurrentThread, Unpark};
+use futures::future::{self, AbortError, AbortHandle, Abortable};
+use futures::stream;
+use futures::sync::{mpsc, oneshot};
+
+mod support;
+use support::*;
+
+struct Count(AtomicUsize);
+
+impl Unpark for Count {
+    fn unpark(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn completes_if_not_aborted() {
+    let (f, _handle) = future::abortable(future::ok::<i32, u32>(1));
+    assert_eq!(f.wait(), Ok(1));
+
+    let (f, _handle) = future::abortable(future::err::<i32, u32>(2));
+    let err = f.wait().err().unwrap();
+    assert!(!err.is_aborted());
+    assert_eq!(err.into_inner(), Some(2));
+}
+
+#[test]
+fn abort_before_poll() {
+    let (f, handle) = future::abortable(future::ok::<i32, u32>(1));
+    handle.abort();
+    assert!(f.is_aborted());
+    let err = f.wait().err().unwrap();
+    assert!(err.is_aborted());
+    assert_eq!(err.into_inner(), None);
+}
+
+#[test]
+fn abort_unparks_task() {
+    let (tx, rx) = oneshot::channel::<i32>();
+    let (f, handle) = future::abortable(rx);
+    let mut task = executor::spawn(f);
+    let count = Arc::new(Count(AtomicUsize::new(0)));
+    assert!(task.poll_future(count.clone()).unwrap().is_not_ready());
+    assert_eq!(count.0.load(Ordering::SeqCst), 0);
+
+    handle.clone().abort();
+    assert_eq!(count.0.load(Ordering::SeqCst), 1);
+    assert_eq!(task.poll_future(unpark_panic()), Err(AbortError::Aborted));
+
+    // The wrapped future isn't polled again, even if it's now ready.
+    tx.complete(1);
+    assert_eq!(task.poll_future(unpark_panic()), Err(AbortError::Aborted));
+}
+
+#[test]
+fn abort_from_other_thread() {
+    let (handle, reg) = AbortHandle::new_pair();
+    let f = Abortable::new(future::empty::<(), ()>(), reg);
+    let t = thread::spawn(move || f.wait());
+    handle.abort();
+    assert_eq!(t.join().unwrap(), Err(AbortError::Aborted));
+}
+
+#[test]
+fn abortable_spawned_task() {
+    // Spawned tasks have to have `()` as their error, which works as long as
+    // the `AbortError` is mapped away.
+    let (f, handle) = future::abortable(future::empty::<(), ()>());
+    let (tx, rx) = oneshot::channel();
+    let mut exec = CurrentThread::new();
+    exec.spawn(f.then(move |res| {
+        tx.complete(res);
+        Ok(())
+    }));
+    handle.abort();
+    assert_eq!(exec.run(rx), Ok(Err(AbortError::Aborted)));
+}
+
+#[test]
+fn abortable_stream() {
+    let (mut tx, rx) = mpsc::unbounded::<i32>();
+    let (s, handle) = stream::abortable(rx);
+    let mut task = executor::spawn(s);
+
+    tx.send(1).unwrap();
+    match task.poll_stream(unpark_noop()) {
+        Ok(Async::Ready(Some(1))) => {}
+        _ => panic!("expected the first item"),
+    }
+    assert!(task.poll_stream(unpark_noop()).unwrap().is_not_ready());
+
+    handle.abort();
+    tx.send(2).unwrap();
+    assert_eq!(task.poll_stream(unpark_panic()), Err(AbortError::Aborted));
+}
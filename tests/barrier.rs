@@ -0,0 +1,134 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Future, Async};
+use futures::executor::{self, Unpark};
+use futures::sync::{Barrier, CountDownLatch};
+
+mod support;
+use support::*;
+
+struct Count(AtomicUsize);
+
+impl Unpark for Count {
+    fn unpark(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn count() -> Arc<Count> {
+    Arc::new(Count(AtomicUsize::new(0)))
+}
+
+#[test]
+fn barrier_releases_all_with_one_leader() {
+    let barrier = Barrier::new(3);
+    let mut a = executor::spawn(barrier.wait());
+    let mut b = executor::spawn(barrier.wait());
+    let (ca, cb) = (count(), count());
+    assert!(a.poll_future(ca.clone()).unwrap().is_not_ready());
+    assert!(b.poll_future(cb.clone()).unwrap().is_not_ready());
+
+    // The last to arrive is the leader, and wakes up everyone else.
+    let res = barrier.wait().wait().unwrap();
+    assert!(res.is_leader());
+    assert_eq!(ca.0.load(Ordering::SeqCst), 1);
+    assert_eq!(cb.0.load(Ordering::SeqCst), 1);
+
+    match a.poll_future(unpark_panic()) {
+        Ok(Async::Ready(res)) => assert!(!res.is_leader()),
+        _ => panic!("barrier should have released"),
+    }
+    match b.poll_future(unpark_panic()) {
+        Ok(Async::Ready(res)) => assert!(!res.is_leader()),
+        _ => panic!("barrier should have released"),
+    }
+}
+
+#[test]
+fn barrier_is_reusable() {
+    let barrier = Barrier::new(2);
+    for _ in 0..3 {
+        let mut a = executor::spawn(barrier.wait());
+        assert!(a.poll_future(unpark_noop()).unwrap().is_not_ready());
+        assert!(barrier.wait().wait().unwrap().is_leader());
+        assert!(a.poll_future(unpark_panic()).unwrap().is_ready());
+    }
+}
+
+#[test]
+fn barrier_dropped_waiter_withdraws() {
+    let barrier = Barrier::new(2);
+    let mut a = executor::spawn(barrier.wait());
+    assert!(a.poll_future(unpark_noop()).unwrap().is_not_ready());
+    drop(a);
+
+    let mut b = executor::spawn(barrier.wait());
+    assert!(b.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert!(barrier.wait().wait().unwrap().is_leader());
+}
+
+#[test]
+fn barrier_threads() {
+    const N: usize = 10;
+
+    let barrier = Barrier::new(N);
+    let arrived = Arc::new(AtomicUsize::new(0));
+    let threads = (0..N).map(|_| {
+        let barrier = barrier.clone();
+        let arrived = arrived.clone();
+        thread::spawn(move || {
+            arrived.fetch_add(1, Ordering::SeqCst);
+            let res = barrier.wait().wait().unwrap();
+            assert_eq!(arrived.load(Ordering::SeqCst), N);
+            res.is_leader()
+        })
+    }).collect::<Vec<_>>();
+
+    let leaders = threads.into_iter().map(|t| t.join().unwrap());
+    assert_eq!(leaders.filter(|&l| l).count(), 1);
+}
+
+#[test]
+fn latch() {
+    let latch = CountDownLatch::new(2);
+    let mut a = executor::spawn(latch.wait());
+    let ca = count();
+    assert!(a.poll_future(ca.clone()).unwrap().is_not_ready());
+
+    latch.count_down();
+    assert_eq!(latch.count(), 1);
+    assert_eq!(ca.0.load(Ordering::SeqCst), 0);
+
+    latch.clone().count_down();
+    assert_eq!(latch.count(), 0);
+    assert_eq!(ca.0.load(Ordering::SeqCst), 1);
+    assert!(a.poll_future(unpark_panic()).unwrap().is_ready());
+
+    // Once open, the latch stays open.
+    latch.count_down();
+    assert_eq!(latch.count(), 0);
+    assert_eq!(latch.wait().wait(), Ok(()));
+}
+
+#[test]
+fn latch_threads() {
+    const N: usize = 10;
+
+    let latch = CountDownLatch::new(N);
+    let done = Arc::new(AtomicUsize::new(0));
+    for _ in 0..N {
+        let latch = latch.clone();
+        let done = done.clone();
+        thread::spawn(move || {
+            done.fetch_add(1, Ordering::SeqCst);
+            latch.count_down();
+        });
+    }
+
+    latch.wait().wait().unwrap();
+    assert_eq!(done.load(Ordering::SeqCst), N);
+}
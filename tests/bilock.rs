@@ -100,3 +100,17 @@ fn concurrent() {
         }
     }
 }
+
+#[test]
+fn reunite() {
+    let (a, b) = BiLock::new(1);
+    let (c, d) = BiLock::new(2);
+
+    let err = a.reunite(d).unwrap_err();
+    let (a, d) = (err.0, err.1);
+    let err = c.reunite(b).unwrap_err();
+    let (c, b) = (err.0, err.1);
+
+    assert_eq!(b.reunite(a).unwrap(), 1);
+    assert_eq!(d.reunite(c).unwrap(), 2);
+}
@@ -0,0 +1,112 @@
+extern crate futures;
+
+use std::thread;
+
+use futures::{Future, Stream, Async};
+use futures::executor;
+use futures::sync::broadcast;
+
+mod support;
+use support::*;
+
+fn is_send<T: Send>() {}
+
+#[test]
+fn bounds() {
+    is_send::<broadcast::Sender<i32>>();
+    is_send::<broadcast::Receiver<i32>>();
+}
+
+#[test]
+fn every_receiver_sees_every_message() {
+    let (tx, mut rx1) = broadcast::channel(4);
+    let mut rx2 = rx1.clone();
+
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    sassert_next(&mut rx1, 1);
+    let mut rx3 = rx1.clone();
+    sassert_next(&mut rx1, 2);
+    sassert_next(&mut rx2, 1);
+    sassert_next(&mut rx2, 2);
+    sassert_next(&mut rx3, 2);
+    sassert_empty(&mut rx1);
+
+    drop(tx);
+    sassert_done(&mut rx1);
+    sassert_done(&mut rx2);
+    sassert_done(&mut rx3);
+}
+
+#[test]
+fn subscribe_sees_only_new_messages() {
+    let (tx, _rx) = broadcast::channel(4);
+    tx.send(1).unwrap();
+
+    let mut rx = tx.subscribe();
+    assert_eq!(tx.receiver_count(), 2);
+    tx.send(2).unwrap();
+    sassert_next(&mut rx, 2);
+    sassert_empty(&mut rx);
+}
+
+#[test]
+fn lagging_receiver() {
+    let (tx, mut rx) = broadcast::channel(2);
+    for i in 0..5 {
+        tx.send(i).unwrap();
+    }
+
+    match executor::spawn(&mut rx).poll_stream(unpark_noop()) {
+        Err(e) => assert_eq!(e.skipped(), 3),
+        Ok(_) => panic!("receiver should have lagged"),
+    }
+    sassert_next(&mut rx, 3);
+    sassert_next(&mut rx, 4);
+    sassert_empty(&mut rx);
+}
+
+#[test]
+fn send_without_receivers() {
+    let (tx, rx) = broadcast::channel(2);
+    drop(rx);
+    assert_eq!(tx.send(1).unwrap_err().into_inner(), 1);
+}
+
+#[test]
+fn sender_wakes_receivers() {
+    let (tx, rx) = broadcast::channel::<u32>(1);
+    let mut a = executor::spawn(rx.clone().into_future());
+    let mut b = executor::spawn(rx.into_future());
+    assert!(a.poll_future(unpark_noop()).ok().unwrap().is_not_ready());
+    assert!(b.poll_future(unpark_noop()).ok().unwrap().is_not_ready());
+
+    let t = thread::spawn(move || {
+        tx.send(7).unwrap();
+    });
+    assert_eq!(a.wait_future().ok().unwrap().0, Some(7));
+    assert_eq!(b.wait_future().ok().unwrap().0, Some(7));
+    t.join().unwrap();
+}
+
+#[test]
+fn sink() {
+    let (tx, rx) = broadcast::channel(8);
+    let rx2 = rx.clone();
+    let t = thread::spawn(move || {
+        let (tx, _) = futures::Sink::send_all(tx, futures::stream::iter((0..5).map(Ok)))
+            .wait().ok().unwrap();
+
+        // Receivers only see the end of the stream once the sender is gone.
+        drop(tx);
+    });
+
+    assert_eq!(rx.collect().wait().unwrap(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(rx2.collect().wait().unwrap(), vec![0, 1, 2, 3, 4]);
+    t.join().unwrap();
+
+    let (mut tx, rx) = broadcast::channel::<u32>(1);
+    drop(rx);
+    assert!(futures::Sink::start_send(&mut tx, 1).is_err());
+    assert_eq!(futures::Sink::poll_complete(&mut tx).ok(), Some(Async::Ready(())));
+}
@@ -0,0 +1,131 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::Future;
+use futures::executor::{self, Unpark};
+use futures::future::{self, CancellationError};
+use futures::sync::{oneshot, CancellationToken};
+
+mod support;
+use support::*;
+
+struct Count(AtomicUsize);
+
+impl Unpark for Count {
+    fn unpark(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn count() -> Arc<Count> {
+    Arc::new(Count(AtomicUsize::new(0)))
+}
+
+#[test]
+fn cancel_wakes_waiters() {
+    let token = CancellationToken::new();
+    let mut a = executor::spawn(token.cancelled());
+    let mut b = executor::spawn(token.clone().cancelled());
+    let (ca, cb) = (count(), count());
+    assert!(a.poll_future(ca.clone()).unwrap().is_not_ready());
+    assert!(b.poll_future(cb.clone()).unwrap().is_not_ready());
+    assert!(!token.is_cancelled());
+
+    token.clone().cancel();
+    assert!(token.is_cancelled());
+    assert_eq!(ca.0.load(Ordering::SeqCst), 1);
+    assert_eq!(cb.0.load(Ordering::SeqCst), 1);
+    assert!(a.poll_future(unpark_panic()).unwrap().is_ready());
+    assert!(b.poll_future(unpark_panic()).unwrap().is_ready());
+
+    // Cancelling again does nothing, and new waiters resolve immediately.
+    token.cancel();
+    assert_eq!(token.cancelled().wait(), Ok(()));
+}
+
+#[test]
+fn dropped_waiter_is_not_woken() {
+    let token = CancellationToken::new();
+    let mut a = executor::spawn(token.cancelled());
+    let ca = count();
+    assert!(a.poll_future(ca.clone()).unwrap().is_not_ready());
+    drop(a);
+    token.cancel();
+    assert_eq!(ca.0.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn child_tokens() {
+    let parent = CancellationToken::new();
+    let child = parent.child_token();
+    let grandchild = child.child_token();
+    let sibling = parent.child_token();
+
+    let mut wait = executor::spawn(grandchild.cancelled());
+    let c = count();
+    assert!(wait.poll_future(c.clone()).unwrap().is_not_ready());
+
+    // Cancelling a child doesn't affect its parent or siblings.
+    child.cancel();
+    assert!(child.is_cancelled());
+    assert!(grandchild.is_cancelled());
+    assert_eq!(c.0.load(Ordering::SeqCst), 1);
+    assert!(!parent.is_cancelled());
+    assert!(!sibling.is_cancelled());
+
+    parent.cancel();
+    assert!(sibling.is_cancelled());
+
+    // Children of a cancelled token start out cancelled.
+    assert!(parent.child_token().is_cancelled());
+}
+
+#[test]
+fn dropped_children_are_skipped() {
+    let parent = CancellationToken::new();
+    for _ in 0..100 {
+        drop(parent.child_token());
+    }
+    let child = parent.child_token();
+    parent.cancel();
+    assert!(child.is_cancelled());
+}
+
+#[test]
+fn cancel_from_other_thread() {
+    let token = CancellationToken::new();
+    let children = (0..10).map(|_| {
+        let child = token.child_token();
+        thread::spawn(move || child.cancelled().wait())
+    }).collect::<Vec<_>>();
+
+    token.cancel();
+    for t in children {
+        t.join().unwrap().unwrap();
+    }
+}
+
+#[test]
+fn with_cancellation() {
+    let token = CancellationToken::new();
+    assert_eq!(future::ok::<i32, u32>(1).with_cancellation(&token).wait(), Ok(1));
+    assert_eq!(future::err::<i32, u32>(2).with_cancellation(&token).wait(),
+               Err(CancellationError::Inner(2)));
+
+    let (tx, rx) = oneshot::channel::<i32>();
+    let mut task = executor::spawn(rx.with_cancellation(&token.child_token()));
+    let c = count();
+    assert!(task.poll_future(c.clone()).unwrap().is_not_ready());
+
+    token.cancel();
+    assert_eq!(c.0.load(Ordering::SeqCst), 1);
+    let err = task.poll_future(unpark_panic()).err().unwrap();
+    assert!(err.is_cancelled());
+
+    // The inner future is dropped as soon as the token is cancelled.
+    assert!(task.get_ref().get_ref().is_none());
+    assert!(tx.is_canceled());
+}
@@ -0,0 +1,151 @@
+extern crate futures;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::thread;
+
+use futures::{Future, Stream, Async, Poll};
+use futures::executor::CurrentThread;
+use futures::future;
+use futures::sync::{mpsc, oneshot};
+
+#[test]
+fn run_root_only() {
+    let mut executor = CurrentThread::new();
+    assert_eq!(executor.run(future::ok::<i32, ()>(1)), Ok(1));
+    assert_eq!(executor.run(future::err::<(), i32>(2)), Err(2));
+}
+
+#[test]
+fn spawned_futures_share_rc_state() {
+    let state = Rc::new(RefCell::new(Vec::new()));
+    let mut executor = CurrentThread::new();
+
+    for i in 0..5 {
+        let state = state.clone();
+        executor.spawn(future::lazy(move || {
+            state.borrow_mut().push(i);
+            Ok(())
+        }));
+    }
+
+    let (tx, rx) = oneshot::channel::<()>();
+    let spawner = executor.spawner();
+    let state2 = state.clone();
+    spawner.spawn(future::lazy(move || {
+        state2.borrow_mut().push(5);
+        tx.complete(());
+        Ok(())
+    }));
+
+    executor.run(rx).unwrap();
+    state.borrow_mut().sort();
+    assert_eq!(*state.borrow(), vec![0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn spawn_from_spawned_future() {
+    let done = Rc::new(Cell::new(false));
+    let mut executor = CurrentThread::new();
+    let spawner = executor.spawner();
+
+    let (tx, rx) = oneshot::channel::<()>();
+    let done2 = done.clone();
+    executor.spawn(future::lazy(move || {
+        spawner.spawn(future::lazy(move || {
+            done2.set(true);
+            tx.complete(());
+            Ok(())
+        }));
+        Ok(())
+    }));
+
+    executor.run(rx).unwrap();
+    assert!(done.get());
+}
+
+#[test]
+fn wakeups_from_other_threads() {
+    let (tx, rx) = mpsc::channel::<u32>(1);
+    let total = Rc::new(Cell::new(0));
+    let (done_tx, done_rx) = oneshot::channel::<()>();
+
+    let mut executor = CurrentThread::new();
+    let total2 = total.clone();
+    executor.spawn(rx.for_each(move |i| {
+        total2.set(total2.get() + i);
+        Ok(())
+    }).then(move |_| {
+        done_tx.complete(());
+        Ok(())
+    }));
+
+    let t = thread::spawn(move || {
+        let mut tx = tx;
+        for i in 1..11 {
+            tx = futures::Sink::send(tx, i).wait().unwrap();
+        }
+    });
+
+    executor.run(done_rx).unwrap();
+    t.join().unwrap();
+    assert_eq!(total.get(), 55);
+}
+
+#[test]
+fn only_notified_tasks_are_polled() {
+    struct Count(Rc<Cell<usize>>, oneshot::Receiver<()>);
+
+    impl Future for Count {
+        type Item = ();
+        type Error = ();
+
+        fn poll(&mut self) -> Poll<(), ()> {
+            self.0.set(self.0.get() + 1);
+            self.1.poll().map_err(|_| ())
+        }
+    }
+
+    let idle_polls = Rc::new(Cell::new(0));
+    let (_idle_tx, idle_rx) = oneshot::channel();
+    let mut executor = CurrentThread::new();
+    executor.spawn(Count(idle_polls.clone(), idle_rx));
+
+    let mut n = 0;
+    let busy = future::poll_fn(move || -> Poll<(), ()> {
+        n += 1;
+        if n == 10 {
+            return Ok(Async::Ready(()))
+        }
+        futures::task::park().unpark();
+        Ok(Async::NotReady)
+    });
+
+    executor.run(busy).unwrap();
+    assert_eq!(idle_polls.get(), 1);
+}
+
+#[test]
+fn unfinished_tasks_are_dropped() {
+    struct SetOnDrop(Rc<Cell<bool>>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let dropped = Rc::new(Cell::new(false));
+    let executor = CurrentThread::new();
+    let guard = SetOnDrop(dropped.clone());
+    executor.spawn(future::empty::<(), ()>().map(move |()| drop(guard)));
+    let spawner = executor.spawner();
+    drop(executor);
+    assert!(dropped.get());
+
+    // Spawning onto a dropped executor just drops the future.
+    let dropped = Rc::new(Cell::new(false));
+    let guard = SetOnDrop(dropped.clone());
+    spawner.spawn(future::empty::<(), ()>().map(move |()| drop(guard)));
+    assert!(dropped.get());
+}
@@ -0,0 +1,116 @@
+extern crate futures;
+
+use futures::{Future, Sink, Async};
+use futures::future::{self, Either};
+use futures::stream;
+use futures::sync::oneshot;
+
+mod support;
+use support::*;
+
+#[test]
+fn select2_first_wins() {
+    let (_tx, rx) = oneshot::channel::<String>();
+    match future::ok::<u32, ()>(1).select2(rx).wait() {
+        Ok(Either::A((1, _rx))) => {}
+        _ => panic!("expected the first future to win"),
+    }
+}
+
+#[test]
+fn select2_second_wins() {
+    let (tx, rx) = oneshot::channel::<&'static str>();
+    tx.complete("hello");
+    let res = future::empty::<u32, ()>().select2(rx).wait();
+    match res {
+        Ok(Either::B(("hello", _empty))) => {}
+        _ => panic!("expected the second future to win"),
+    }
+}
+
+#[test]
+fn select2_keeps_loser_running() {
+    let (tx1, rx1) = oneshot::channel::<u32>();
+    let (tx2, rx2) = oneshot::channel::<&'static str>();
+
+    let mut f = rx1.select2(rx2);
+    match futures::executor::spawn(&mut f).poll_future(unpark_noop()) {
+        Ok(Async::NotReady) => {}
+        _ => panic!("neither future should be ready yet"),
+    }
+
+    tx2.complete("second");
+    let rx1 = match f.wait() {
+        Ok(Either::B(("second", rx1))) => rx1,
+        _ => panic!("expected the second future to win"),
+    };
+
+    tx1.complete(3);
+    assert_eq!(rx1.wait(), Ok(3));
+}
+
+#[test]
+fn select2_errors() {
+    let res = future::err::<(), u32>(4).select2(future::empty::<(), bool>()).wait();
+    match res {
+        Err(Either::A((4, _))) => {}
+        _ => panic!("expected an error from the first future"),
+    }
+
+    let res = future::empty::<(), u32>().select2(future::err::<(), bool>(true)).wait();
+    match res {
+        Err(Either::B((true, _))) => {}
+        _ => panic!("expected an error from the second future"),
+    }
+}
+
+#[test]
+fn split() {
+    let either: Either<(u32, char), (u32, bool)> = Either::B((3, true));
+    match either.split() {
+        (3, Either::B(true)) => {}
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn either_future() {
+    fn pick(a: bool) -> Either<future::Ok<u32, ()>, future::Map<future::Ok<u32, ()>, fn(u32) -> u32>> {
+        fn double(x: u32) -> u32 { x * 2 }
+        if a {
+            Either::A(future::ok(1))
+        } else {
+            Either::B(future::ok(1).map(double as fn(u32) -> u32))
+        }
+    }
+
+    assert_done(|| pick(true), Ok(1));
+    assert_done(|| pick(false), Ok(2));
+}
+
+#[test]
+fn either_stream() {
+    let mut s: Either<_, stream::Empty<i32, u32>> =
+        Either::A(stream::iter(vec![Ok::<i32, u32>(1), Ok(2)]));
+    sassert_next(&mut s, 1);
+    sassert_next(&mut s, 2);
+    sassert_done(&mut s);
+
+    let mut s: Either<stream::IterStream<std::vec::IntoIter<Result<i32, u32>>>, _> =
+        Either::B(stream::empty::<i32, u32>());
+    sassert_done(&mut s);
+}
+
+#[test]
+fn either_sink() {
+    let sink: Either<Vec<i32>, Vec<i32>> = Either::B(Vec::new());
+    let sink = sink.send(1).wait().unwrap().send(2).wait().unwrap();
+    match sink {
+        Either::B(v) => assert_eq!(v, vec![1, 2]),
+        Either::A(_) => panic!(),
+    }
+
+    let mut sink: Either<Vec<i32>, Vec<i32>> = Either::A(Vec::new());
+    assert!(sink.start_send(5).unwrap().is_ready());
+    assert_eq!(sink.poll_complete(), Ok(Async::Ready(())));
+}
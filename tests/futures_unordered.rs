@@ -0,0 +1,148 @@
+extern crate futures;
+
+use std::any::Any;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::{Future, Async, Poll};
+use futures::executor;
+use futures::future;
+use futures::stream::{futures_unordered, FuturesUnordered};
+use futures::sync::oneshot;
+
+mod support;
+use support::*;
+
+#[test]
+fn works_1() {
+    let (a_tx, a_rx) = oneshot::channel::<u32>();
+    let (b_tx, b_rx) = oneshot::channel::<u32>();
+    let (c_tx, c_rx) = oneshot::channel::<u32>();
+
+    let stream = futures_unordered(vec![a_rx, b_rx, c_rx]);
+
+    let mut spawn = executor::spawn(stream);
+    b_tx.complete(99);
+    assert_eq!(Some(Ok(99)), spawn.wait_stream());
+
+    a_tx.complete(33);
+    c_tx.complete(33);
+    assert_eq!(Some(Ok(33)), spawn.wait_stream());
+    assert_eq!(Some(Ok(33)), spawn.wait_stream());
+    assert_eq!(None, spawn.wait_stream());
+}
+
+#[test]
+fn works_2() {
+    let (a_tx, a_rx) = oneshot::channel::<u32>();
+    let (b_tx, b_rx) = oneshot::channel::<u32>();
+    let (c_tx, c_rx) = oneshot::channel::<u32>();
+
+    let stream = futures_unordered(vec![
+        a_rx.boxed(),
+        b_rx.join(c_rx).map(|(a, b)| a + b).boxed(),
+    ]);
+
+    let mut spawn = executor::spawn(stream);
+    a_tx.complete(33);
+    b_tx.complete(33);
+    assert!(spawn.poll_stream(unpark_noop()).unwrap().is_ready());
+    c_tx.complete(33);
+    assert!(spawn.poll_stream(unpark_noop()).unwrap().is_ready());
+}
+
+#[test]
+fn finished_future_ok() {
+    let (_a_tx, a_rx) = oneshot::channel::<Box<Any+Send>>();
+    let (b_tx, b_rx) = oneshot::channel::<Box<Any+Send>>();
+    let (c_tx, c_rx) = oneshot::channel::<Box<Any+Send>>();
+
+    let stream = futures_unordered(vec![
+        a_rx.boxed(),
+        b_rx.select(c_rx).then(|res| Ok(Box::new(res) as Box<Any+Send>)).boxed(),
+    ]);
+
+    let mut spawn = executor::spawn(stream);
+    for _ in 0..10 {
+        assert!(spawn.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    }
+
+    b_tx.complete(Box::new(()));
+    let next = spawn.poll_stream(unpark_noop()).unwrap();
+    assert!(next.is_ready());
+    c_tx.complete(Box::new(()));
+    assert!(spawn.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    assert!(spawn.poll_stream(unpark_noop()).unwrap().is_not_ready());
+}
+
+#[test]
+fn empty_is_done() {
+    let mut set = FuturesUnordered::<future::Ok<i32, u32>>::new();
+    assert!(set.is_empty());
+    sassert_done(&mut set);
+}
+
+#[test]
+fn push_while_running() {
+    let (a_tx, a_rx) = oneshot::channel::<u32>();
+    let (b_tx, b_rx) = oneshot::channel::<u32>();
+
+    let mut set = FuturesUnordered::new();
+    set.push(a_rx);
+    assert_eq!(set.len(), 1);
+
+    let mut spawn = executor::spawn(set);
+    assert!(spawn.poll_stream(unpark_noop()).unwrap().is_not_ready());
+
+    spawn.get_mut().push(b_rx);
+    assert_eq!(spawn.get_ref().len(), 2);
+    b_tx.complete(2);
+    assert_eq!(Some(Ok(2)), spawn.wait_stream());
+    assert_eq!(spawn.get_ref().len(), 1);
+
+    a_tx.complete(1);
+    assert_eq!(Some(Ok(1)), spawn.wait_stream());
+    assert_eq!(None, spawn.wait_stream());
+
+    // Slots freed by completed futures are reused by new ones.
+    let (c_tx, c_rx) = oneshot::channel::<u32>();
+    spawn.get_mut().push(c_rx);
+    c_tx.complete(3);
+    assert_eq!(Some(Ok(3)), spawn.wait_stream());
+    assert_eq!(None, spawn.wait_stream());
+}
+
+#[test]
+fn only_notified_futures_are_polled() {
+    struct Counted(Arc<AtomicUsize>, oneshot::Receiver<u32>);
+
+    impl Future for Counted {
+        type Item = u32;
+        type Error = oneshot::Canceled;
+
+        fn poll(&mut self) -> Poll<u32, oneshot::Canceled> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            self.1.poll()
+        }
+    }
+
+    let polls = Arc::new(AtomicUsize::new(0));
+    let mut txs = Vec::new();
+    let mut set = FuturesUnordered::new();
+    for _ in 0..10 {
+        let (tx, rx) = oneshot::channel();
+        txs.push(tx);
+        set.push(Counted(polls.clone(), rx));
+    }
+
+    let mut spawn = executor::spawn(set);
+    assert!(spawn.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    assert_eq!(polls.load(Ordering::SeqCst), 10);
+
+    txs.pop().unwrap().complete(9);
+    match spawn.poll_stream(unpark_noop()) {
+        Ok(Async::Ready(Some(9))) => {}
+        _ => panic!("expected the completed future"),
+    }
+    assert_eq!(polls.load(Ordering::SeqCst), 11);
+}
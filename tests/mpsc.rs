@@ -278,6 +278,139 @@ fn stress_receiver_multi_task_bounded_hard() {
     assert_eq!(AMT, n.load(Ordering::Relaxed));
 }
 
+#[test]
+fn try_send_1() {
+    const N: usize = 3000;
+    let (mut tx, rx) = mpsc::channel(0);
+
+    let t = thread::spawn(move || {
+        for i in 0..N {
+            loop {
+                if tx.try_send(i).is_ok() {
+                    break
+                }
+            }
+        }
+    });
+    for (i, j) in rx.wait().enumerate() {
+        assert_eq!(i, j.unwrap());
+    }
+    t.join().unwrap();
+}
+
+#[test]
+fn try_send_2() {
+    let (mut tx, rx) = mpsc::channel(0);
+
+    tx.try_send("hello").unwrap();
+    let err = tx.try_send("fail").unwrap_err();
+    assert!(err.is_full());
+    assert!(!err.is_disconnected());
+    assert_eq!(err.into_inner(), "fail");
+
+    let mut rx = rx.wait();
+    assert_eq!(rx.next(), Some(Ok("hello")));
+
+    // Reading the message makes room for the sender again.
+    tx.try_send("goodbye").unwrap();
+    assert_eq!(rx.next(), Some(Ok("goodbye")));
+
+    drop(rx);
+    let err = tx.try_send("gone").unwrap_err();
+    assert!(err.is_disconnected());
+    assert_eq!(err.into_inner(), "gone");
+}
+
+#[test]
+fn poll_ready() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+
+    lazy(move || {
+        assert!(tx.poll_ready().unwrap().is_ready());
+        assert!(is_ready(&tx.start_send(1).unwrap()));
+        assert!(tx.poll_ready().unwrap().is_not_ready());
+
+        assert_eq!(rx.poll().unwrap(), Async::Ready(Some(1)));
+        assert!(tx.poll_ready().unwrap().is_ready());
+
+        drop(rx);
+        assert!(tx.poll_ready().is_err());
+
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[test]
+fn try_recv() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+    assert_eq!(rx.try_recv(), Err(mpsc::TryRecvError::Empty));
+
+    tx.try_send(1).unwrap();
+    tx.try_send(2).unwrap();
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Ok(2));
+    assert_eq!(rx.try_recv(), Err(mpsc::TryRecvError::Empty));
+
+    tx.try_send(3).unwrap();
+    drop(tx);
+    assert_eq!(rx.try_recv(), Ok(3));
+    assert_eq!(rx.try_recv(), Err(mpsc::TryRecvError::Disconnected));
+    assert_eq!(rx.try_recv(), Err(mpsc::TryRecvError::Disconnected));
+
+    let (mut tx, mut rx) = mpsc::unbounded::<i32>();
+    mpsc::UnboundedSender::send(&mut tx, 1).unwrap();
+    rx.close();
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(mpsc::TryRecvError::Disconnected));
+}
+
+#[test]
+fn len_and_capacity() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(2);
+    assert_eq!(tx.capacity(), 3);
+    assert_eq!(rx.capacity(), 3);
+    let tx2 = tx.clone();
+    assert_eq!(tx.capacity(), 4);
+    drop(tx2);
+
+    assert!(tx.is_empty());
+    tx.try_send(1).unwrap();
+    tx.try_send(2).unwrap();
+    assert_eq!(tx.len(), 2);
+    assert_eq!(rx.len(), 2);
+    assert!(!rx.is_empty());
+
+    rx.try_recv().unwrap();
+    assert_eq!(rx.len(), 1);
+
+    // The termination message isn't counted.
+    drop(tx);
+    assert_eq!(rx.len(), 1);
+    rx.try_recv().unwrap();
+    assert!(rx.is_empty());
+
+    let (mut tx, mut rx) = mpsc::unbounded::<i32>();
+    mpsc::UnboundedSender::send(&mut tx, 1).unwrap();
+    assert_eq!(tx.len(), 1);
+    assert_eq!(rx.len(), 1);
+    assert!(!rx.is_empty());
+    rx.try_recv().unwrap();
+    assert!(tx.is_empty());
+}
+
+#[test]
+fn is_closed() {
+    let (tx, mut rx) = mpsc::channel::<i32>(1);
+    assert!(!tx.is_closed());
+    rx.close();
+    assert!(tx.is_closed());
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    assert!(!tx.is_closed());
+    drop(rx);
+    assert!(tx.is_closed());
+}
+
 fn is_ready<T>(res: &AsyncSink<T>) -> bool {
     match *res {
         AsyncSink::Ready => true,
@@ -0,0 +1,129 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Future, Async};
+use futures::executor::{self, Unpark};
+use futures::sync::Mutex;
+
+mod support;
+use support::*;
+
+struct Count(AtomicUsize);
+
+impl Unpark for Count {
+    fn unpark(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn count() -> Arc<Count> {
+    Arc::new(Count(AtomicUsize::new(0)))
+}
+
+#[test]
+fn smoke() {
+    let mutex = Mutex::new(1);
+    let mut guard = mutex.lock().wait().unwrap();
+    assert_eq!(*guard, 1);
+    *guard = 2;
+    assert!(mutex.try_lock().is_none());
+    drop(guard);
+
+    assert_eq!(*mutex.try_lock().unwrap(), 2);
+    assert_eq!(*mutex.clone().lock().wait().unwrap(), 2);
+}
+
+#[test]
+fn waiters_are_woken_in_order() {
+    let mutex = Mutex::new(Vec::new());
+    let guard = mutex.try_lock().unwrap();
+
+    let mut waiters = (0..3).map(|i| {
+        let f = mutex.lock().map(move |mut g| g.push(i));
+        executor::spawn(f)
+    }).collect::<Vec<_>>();
+    let counts = (0..3).map(|_| count()).collect::<Vec<_>>();
+
+    // Poll them in reverse order to get in line, then make sure they get the
+    // lock in that same order.
+    for i in (0..3).rev() {
+        assert!(waiters[i].poll_future(counts[i].clone()).unwrap().is_not_ready());
+    }
+
+    // A new arrival can't jump the queue while others are waiting.
+    drop(guard);
+    assert!(mutex.try_lock().is_none());
+    assert_eq!(counts[2].0.load(Ordering::SeqCst), 1);
+    assert_eq!(counts[1].0.load(Ordering::SeqCst), 0);
+
+    assert!(waiters[2].poll_future(unpark_panic()).unwrap().is_ready());
+    assert_eq!(counts[1].0.load(Ordering::SeqCst), 1);
+    assert!(waiters[1].poll_future(unpark_panic()).unwrap().is_ready());
+    assert!(waiters[0].poll_future(unpark_panic()).unwrap().is_ready());
+
+    assert_eq!(*mutex.try_lock().unwrap(), vec![2, 1, 0]);
+}
+
+#[test]
+fn dropped_waiter_passes_lock_on() {
+    let mutex = Mutex::new(0);
+    let guard = mutex.try_lock().unwrap();
+
+    let mut a = executor::spawn(mutex.lock());
+    let mut b = executor::spawn(mutex.lock());
+    let (ca, cb) = (count(), count());
+    assert!(a.poll_future(ca.clone()).unwrap().is_not_ready());
+    assert!(b.poll_future(cb.clone()).unwrap().is_not_ready());
+
+    // `a` is handed the lock, but goes away before picking it up.
+    drop(guard);
+    assert_eq!(ca.0.load(Ordering::SeqCst), 1);
+    drop(a);
+    assert_eq!(cb.0.load(Ordering::SeqCst), 1);
+
+    match b.poll_future(unpark_panic()) {
+        Ok(Async::Ready(_guard)) => {}
+        _ => panic!("lock should have been passed on"),
+    }
+}
+
+#[test]
+fn dropped_waiter_leaves_queue() {
+    let mutex = Mutex::new(0);
+    let guard = mutex.try_lock().unwrap();
+
+    let mut a = executor::spawn(mutex.lock());
+    assert!(a.poll_future(unpark_panic()).unwrap().is_not_ready());
+    drop(a);
+
+    drop(guard);
+    assert!(mutex.try_lock().is_some());
+}
+
+#[test]
+fn many_tasks() {
+    const TASKS: usize = 20;
+    const ITERS: usize = 100;
+
+    let mutex = Mutex::new(0);
+    let threads = (0..TASKS).map(|_| {
+        let mutex = mutex.clone();
+        thread::spawn(move || {
+            for _ in 0..ITERS {
+                mutex.lock().and_then(|mut guard| {
+                    let prev = *guard;
+                    // Yield while holding the lock to shake out races.
+                    delay_future(Ok(())).map(move |()| *guard = prev + 1)
+                }).wait().unwrap();
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    for t in threads {
+        t.join().unwrap();
+    }
+    assert_eq!(*mutex.try_lock().unwrap(), TASKS * ITERS);
+}
@@ -0,0 +1,121 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Future, Async, Poll};
+use futures::executor::{self, Unpark};
+use futures::future;
+use futures::sync::{AtomicTask, Notify};
+
+mod support;
+use support::*;
+
+struct Count(AtomicUsize);
+
+impl Unpark for Count {
+    fn unpark(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn count() -> Arc<Count> {
+    Arc::new(Count(AtomicUsize::new(0)))
+}
+
+#[test]
+fn atomic_task_notifies_last_registered() {
+    let task = Arc::new(AtomicTask::new());
+
+    // Notifying before anything is registered does nothing.
+    task.notify();
+
+    let (c1, c2) = (count(), count());
+    let task2 = task.clone();
+    let mut a = executor::spawn(future::poll_fn(move || -> Poll<(), ()> {
+        task2.register();
+        Ok(Async::NotReady)
+    }));
+    assert!(a.poll_future(c1.clone()).unwrap().is_not_ready());
+    assert!(a.poll_future(c2.clone()).unwrap().is_not_ready());
+
+    task.notify();
+    assert_eq!(c1.0.load(Ordering::SeqCst), 0);
+    assert_eq!(c2.0.load(Ordering::SeqCst), 1);
+
+    // The task is only notified once per registration.
+    task.notify();
+    assert_eq!(c2.0.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn atomic_task_no_lost_wakeups() {
+    const N: usize = 1000;
+
+    struct Counter {
+        task: Arc<AtomicTask>,
+        ready: Arc<AtomicUsize>,
+        seen: usize,
+    }
+
+    impl Future for Counter {
+        type Item = ();
+        type Error = ();
+
+        fn poll(&mut self) -> Poll<(), ()> {
+            self.task.register();
+            self.seen = self.ready.load(Ordering::SeqCst);
+            if self.seen == N {
+                Ok(Async::Ready(()))
+            } else {
+                Ok(Async::NotReady)
+            }
+        }
+    }
+
+    let task = Arc::new(AtomicTask::new());
+    let ready = Arc::new(AtomicUsize::new(0));
+    let (task2, ready2) = (task.clone(), ready.clone());
+    let t = thread::spawn(move || {
+        for _ in 0..N {
+            ready2.fetch_add(1, Ordering::SeqCst);
+            task2.notify();
+        }
+    });
+
+    Counter { task: task, ready: ready, seen: 0 }.wait().unwrap();
+    t.join().unwrap();
+}
+
+#[test]
+fn notify_stores_a_permit() {
+    let notify = Notify::new();
+    notify.notify();
+    notify.notify();
+
+    // Notifications are coalesced into a single permit.
+    assert_eq!(notify.notified().wait(), Ok(()));
+    let mut f = executor::spawn(notify.notified());
+    let c = count();
+    assert!(f.poll_future(c.clone()).unwrap().is_not_ready());
+
+    notify.clone().notify();
+    assert_eq!(c.0.load(Ordering::SeqCst), 1);
+    assert!(f.poll_future(unpark_panic()).unwrap().is_ready());
+}
+
+#[test]
+fn notify_threads() {
+    let notify = Notify::new();
+    let done = Arc::new(AtomicBool::new(false));
+    let (notify2, done2) = (notify.clone(), done.clone());
+    let t = thread::spawn(move || {
+        done2.store(true, Ordering::SeqCst);
+        notify2.notify();
+    });
+
+    notify.notified().wait().unwrap();
+    assert!(done.load(Ordering::SeqCst));
+    t.join().unwrap();
+}
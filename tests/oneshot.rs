@@ -1,6 +1,6 @@
 extern crate futures;
 
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc, Barrier};
 use std::thread;
 
 use futures::{Future, Poll};
@@ -89,3 +89,83 @@ fn close_wakes() {
     tx2.send(()).unwrap();
     t.join().unwrap();
 }
+
+#[test]
+fn send_returns_value_when_canceled() {
+    let (tx, rx) = channel::<u32>();
+    assert!(!tx.is_canceled());
+    drop(rx);
+    assert!(tx.is_canceled());
+    assert_eq!(tx.send(1), Err(1));
+
+    let (tx, mut rx) = channel::<u32>();
+    rx.close();
+    assert!(tx.is_canceled());
+    assert_eq!(tx.send(2), Err(2));
+
+    let (tx, rx) = channel::<u32>();
+    assert_eq!(tx.send(3), Ok(()));
+    assert_eq!(rx.wait(), Ok(3));
+}
+
+#[test]
+fn try_recv() {
+    let (tx, mut rx) = channel::<u32>();
+    assert_eq!(rx.try_recv(), Ok(None));
+    tx.send(1).unwrap();
+    assert_eq!(rx.try_recv(), Ok(Some(1)));
+    assert_eq!(rx.try_recv(), Err(Canceled));
+
+    let (tx, mut rx) = channel::<u32>();
+    drop(tx);
+    assert_eq!(rx.try_recv(), Err(Canceled));
+}
+
+#[test]
+fn send_recv_race() {
+    for _ in 0..500 {
+        let (tx, rx) = channel::<u32>();
+        let t = thread::spawn(move || drop(rx));
+
+        // Either the value is delivered or handed back, never lost.
+        let _ = tx.send(1);
+        t.join().unwrap();
+    }
+
+    for _ in 0..500 {
+        let (tx, mut rx) = channel::<u32>();
+        let t = thread::spawn(move || tx.send(1));
+        let res = loop {
+            match rx.try_recv() {
+                Ok(Some(v)) => break v,
+                Ok(None) => thread::yield_now(),
+                Err(Canceled) => panic!("value was lost"),
+            }
+        };
+        assert_eq!(res, 1);
+        assert_eq!(t.join().unwrap(), Ok(()));
+    }
+}
+
+#[test]
+fn close_send_race() {
+    for _ in 0..5000 {
+        let (tx, mut rx) = channel::<u32>();
+        let barrier = Arc::new(Barrier::new(2));
+        let barrier2 = barrier.clone();
+        let t = thread::spawn(move || {
+            barrier2.wait();
+            tx.send(1)
+        });
+        barrier.wait();
+        rx.close();
+
+        // Exactly one side ends up with the value, and polling after closing
+        // never panics.
+        match (rx.wait(), t.join().unwrap()) {
+            (Ok(1), Ok(())) |
+            (Err(Canceled), Err(1)) => {}
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+}
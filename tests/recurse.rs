@@ -2,7 +2,10 @@ extern crate futures;
 
 use std::sync::mpsc::channel;
 
-use futures::future::{ok, Future};
+use futures::future::{ok, loop_fn, Future, Loop};
+
+mod support;
+use support::*;
 
 #[test]
 fn lots() {
@@ -20,3 +23,33 @@ fn lots() {
     });
     rx.recv().unwrap();
 }
+
+#[test]
+fn loop_fn_lots() {
+    let (tx, rx) = channel();
+    ::std::thread::spawn(|| {
+        loop_fn(1_000_000, |n| {
+            if n == 0 {
+                Ok::<_, ()>(Loop::Break(()))
+            } else {
+                Ok(Loop::Continue(n - 1))
+            }
+        }).map(move |_| tx.send(()).unwrap()).wait()
+    });
+    rx.recv().unwrap();
+}
+
+#[test]
+fn loop_fn_not_ready() {
+    let f = loop_fn(0, |n| {
+        delay_future(Ok::<_, u32>(n + 1)).map(|n| {
+            if n == 3 { Loop::Break(n) } else { Loop::Continue(n) }
+        })
+    });
+    assert_done(|| f, Ok(3));
+
+    let f = loop_fn(0, |n| {
+        if n == 2 { Err(n) } else { Ok(Loop::Continue::<(), _>(n + 1)) }
+    });
+    assert_done(|| f, Err(2));
+}
@@ -0,0 +1,165 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Future, Async};
+use futures::executor::{self, Unpark};
+use futures::sync::RwLock;
+
+mod support;
+use support::*;
+
+struct Count(AtomicUsize);
+
+impl Unpark for Count {
+    fn unpark(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn count() -> Arc<Count> {
+    Arc::new(Count(AtomicUsize::new(0)))
+}
+
+#[test]
+fn smoke() {
+    let lock = RwLock::new(1);
+    let a = lock.read().wait().unwrap();
+    let b = lock.try_read().unwrap();
+    assert_eq!(*a + *b, 2);
+    assert!(lock.try_write().is_none());
+    drop((a, b));
+
+    let mut w = lock.clone().write().wait().unwrap();
+    *w = 2;
+    assert!(lock.try_read().is_none());
+    assert!(lock.try_write().is_none());
+    drop(w);
+
+    assert_eq!(*lock.try_read().unwrap(), 2);
+}
+
+#[test]
+fn writer_preference() {
+    let lock = RwLock::new(0);
+    let r1 = lock.try_read().unwrap();
+
+    // A waiting writer blocks new readers from joining the current ones.
+    let mut w = executor::spawn(lock.write());
+    let cw = count();
+    assert!(w.poll_future(cw.clone()).unwrap().is_not_ready());
+    assert!(lock.try_read().is_none());
+
+    let mut r2 = executor::spawn(lock.read());
+    let cr = count();
+    assert!(r2.poll_future(cr.clone()).unwrap().is_not_ready());
+
+    // The writer goes first once the readers are done.
+    drop(r1);
+    assert_eq!(cw.0.load(Ordering::SeqCst), 1);
+    assert_eq!(cr.0.load(Ordering::SeqCst), 0);
+    let mut guard = match w.poll_future(unpark_panic()) {
+        Ok(Async::Ready(guard)) => guard,
+        _ => panic!("writer should have been handed the lock"),
+    };
+    *guard += 1;
+
+    drop(guard);
+    assert_eq!(cr.0.load(Ordering::SeqCst), 1);
+    match r2.poll_future(unpark_panic()) {
+        Ok(Async::Ready(guard)) => assert_eq!(*guard, 1),
+        _ => panic!("reader should have been handed the lock"),
+    }
+}
+
+#[test]
+fn waiting_readers_share_the_lock() {
+    let lock = RwLock::new(0);
+    let w = lock.try_write().unwrap();
+
+    let mut readers = (0..3).map(|_| executor::spawn(lock.read())).collect::<Vec<_>>();
+    for r in readers.iter_mut() {
+        assert!(r.poll_future(unpark_noop()).unwrap().is_not_ready());
+    }
+
+    drop(w);
+    let guards = readers.iter_mut().map(|r| {
+        match r.poll_future(unpark_panic()) {
+            Ok(Async::Ready(guard)) => guard,
+            _ => panic!("all readers should have been handed the lock"),
+        }
+    }).collect::<Vec<_>>();
+    assert!(lock.try_write().is_none());
+    drop(guards);
+    assert!(lock.try_write().is_some());
+}
+
+#[test]
+fn dropped_writer_unblocks_readers() {
+    let lock = RwLock::new(0);
+    let r1 = lock.try_read().unwrap();
+
+    let mut w = executor::spawn(lock.write());
+    assert!(w.poll_future(unpark_noop()).unwrap().is_not_ready());
+    let mut r2 = executor::spawn(lock.read());
+    let cr = count();
+    assert!(r2.poll_future(cr.clone()).unwrap().is_not_ready());
+
+    drop(w);
+    assert_eq!(cr.0.load(Ordering::SeqCst), 1);
+    assert!(r2.poll_future(unpark_panic()).unwrap().is_ready());
+    drop(r1);
+}
+
+#[test]
+fn dropped_writer_passes_lock_on() {
+    let lock = RwLock::new(0);
+    let r = lock.try_read().unwrap();
+
+    let mut w = executor::spawn(lock.write());
+    let mut r2 = executor::spawn(lock.read());
+    let cr = count();
+    assert!(w.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert!(r2.poll_future(cr.clone()).unwrap().is_not_ready());
+
+    // The writer is handed the lock, but goes away before picking it up.
+    drop(r);
+    assert_eq!(cr.0.load(Ordering::SeqCst), 0);
+    drop(w);
+    assert_eq!(cr.0.load(Ordering::SeqCst), 1);
+    assert!(r2.poll_future(unpark_panic()).unwrap().is_ready());
+}
+
+#[test]
+fn many_tasks() {
+    const TASKS: usize = 10;
+    const ITERS: usize = 100;
+
+    let lock = RwLock::new((0, 0));
+    let threads = (0..TASKS).map(|i| {
+        let lock = lock.clone();
+        thread::spawn(move || {
+            for _ in 0..ITERS {
+                if i % 2 == 0 {
+                    lock.write().and_then(|mut guard| {
+                        guard.0 += 1;
+                        // Yield while holding the lock to shake out races.
+                        delay_future(Ok(())).map(move |()| guard.1 += 1)
+                    }).wait().unwrap();
+                } else {
+                    lock.read().and_then(|guard| {
+                        assert_eq!(guard.0, guard.1);
+                        delay_future(Ok(())).map(move |()| drop(guard))
+                    }).wait().unwrap();
+                }
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    for t in threads {
+        t.join().unwrap();
+    }
+    assert_eq!(*lock.try_read().unwrap(), (TASKS / 2 * ITERS, TASKS / 2 * ITERS));
+}
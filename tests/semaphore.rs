@@ -0,0 +1,145 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Future, Async};
+use futures::executor::{self, Unpark};
+use futures::sync::Semaphore;
+
+mod support;
+use support::*;
+
+struct Count(AtomicUsize);
+
+impl Unpark for Count {
+    fn unpark(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn count() -> Arc<Count> {
+    Arc::new(Count(AtomicUsize::new(0)))
+}
+
+#[test]
+fn smoke() {
+    let semaphore = Semaphore::new(3);
+    let a = semaphore.acquire(2).wait().unwrap();
+    assert_eq!(a.permits(), 2);
+    assert_eq!(semaphore.available_permits(), 1);
+    assert!(semaphore.try_acquire(2).is_none());
+
+    let b = semaphore.try_acquire(1).unwrap();
+    assert_eq!(semaphore.available_permits(), 0);
+    drop(a);
+    drop(b);
+    assert_eq!(semaphore.clone().available_permits(), 3);
+}
+
+#[test]
+fn waiters_are_woken_in_order() {
+    let semaphore = Semaphore::new(2);
+    let permit = semaphore.try_acquire(2).unwrap();
+
+    // A large request at the front holds up the smaller one behind it.
+    let mut a = executor::spawn(semaphore.acquire(2));
+    let mut b = executor::spawn(semaphore.acquire(1));
+    let (ca, cb) = (count(), count());
+    assert!(a.poll_future(ca.clone()).unwrap().is_not_ready());
+    assert!(b.poll_future(cb.clone()).unwrap().is_not_ready());
+
+    semaphore.add_permits(1);
+    assert_eq!(ca.0.load(Ordering::SeqCst), 0);
+    assert_eq!(cb.0.load(Ordering::SeqCst), 0);
+    assert!(semaphore.try_acquire(1).is_none());
+
+    // Releasing two more wakes both of them up.
+    drop(permit);
+    assert_eq!(ca.0.load(Ordering::SeqCst), 1);
+    assert_eq!(cb.0.load(Ordering::SeqCst), 1);
+    assert_eq!(semaphore.available_permits(), 0);
+
+    let a = match a.poll_future(unpark_panic()) {
+        Ok(Async::Ready(permit)) => permit,
+        _ => panic!("permits should have been handed out"),
+    };
+    assert!(b.poll_future(unpark_panic()).unwrap().is_ready());
+    assert_eq!(semaphore.available_permits(), 1);
+    drop(a);
+    assert_eq!(semaphore.available_permits(), 3);
+}
+
+#[test]
+fn dropped_waiter_gives_permits_back() {
+    let semaphore = Semaphore::new(1);
+    let permit = semaphore.try_acquire(1).unwrap();
+
+    let mut a = executor::spawn(semaphore.acquire(1));
+    let mut b = executor::spawn(semaphore.acquire(1));
+    let (ca, cb) = (count(), count());
+    assert!(a.poll_future(ca.clone()).unwrap().is_not_ready());
+    assert!(b.poll_future(cb.clone()).unwrap().is_not_ready());
+
+    // `a` is handed the permit, but goes away before picking it up.
+    drop(permit);
+    assert_eq!(ca.0.load(Ordering::SeqCst), 1);
+    drop(a);
+    assert_eq!(cb.0.load(Ordering::SeqCst), 1);
+    assert!(b.poll_future(unpark_panic()).unwrap().is_ready());
+}
+
+#[test]
+fn dropped_waiter_unblocks_queue() {
+    let semaphore = Semaphore::new(1);
+
+    let mut a = executor::spawn(semaphore.acquire(2));
+    let mut b = executor::spawn(semaphore.acquire(1));
+    let cb = count();
+    assert!(a.poll_future(unpark_panic()).unwrap().is_not_ready());
+    assert!(b.poll_future(cb.clone()).unwrap().is_not_ready());
+
+    drop(a);
+    assert_eq!(cb.0.load(Ordering::SeqCst), 1);
+    assert!(b.poll_future(unpark_panic()).unwrap().is_ready());
+}
+
+#[test]
+fn forget() {
+    let semaphore = Semaphore::new(2);
+    semaphore.try_acquire(1).unwrap().forget();
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[test]
+fn bounds_concurrency() {
+    const TASKS: usize = 20;
+    const ITERS: usize = 50;
+    const PERMITS: usize = 3;
+
+    let semaphore = Semaphore::new(PERMITS);
+    let active = Arc::new(AtomicUsize::new(0));
+    let threads = (0..TASKS).map(|_| {
+        let semaphore = semaphore.clone();
+        let active = active.clone();
+        thread::spawn(move || {
+            for _ in 0..ITERS {
+                let active = active.clone();
+                semaphore.acquire(1).and_then(move |permit| {
+                    assert!(active.fetch_add(1, Ordering::SeqCst) < PERMITS);
+                    // Yield while holding the permit to shake out races.
+                    delay_future(Ok(())).map(move |()| {
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        drop(permit);
+                    })
+                }).wait().unwrap();
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    for t in threads {
+        t.join().unwrap();
+    }
+    assert_eq!(semaphore.available_permits(), PERMITS);
+}
@@ -0,0 +1,180 @@
+extern crate futures;
+
+mod support;
+
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use futures::{Future, Async, Poll};
+use futures::executor::{self, Unpark};
+use futures::future;
+use futures::sync::oneshot;
+use futures::task;
+
+use support::*;
+
+fn send_shared_oneshot_and_wait_on_multiple_threads(threads_number: u32) {
+    let (tx, rx) = oneshot::channel::<u32>();
+    let f = rx.shared();
+    let threads = (0..threads_number).map(|_| {
+        let cloned_future = f.clone();
+        thread::spawn(move || {
+            assert_eq!(*cloned_future.wait().unwrap(), 6);
+        })
+    }).collect::<Vec<_>>();
+    tx.complete(6);
+    assert_eq!(*f.wait().unwrap(), 6);
+    for f in threads {
+        f.join().unwrap();
+    }
+}
+
+#[test]
+fn one_thread() {
+    send_shared_oneshot_and_wait_on_multiple_threads(1);
+}
+
+#[test]
+fn two_threads() {
+    send_shared_oneshot_and_wait_on_multiple_threads(2);
+}
+
+#[test]
+fn many_threads() {
+    send_shared_oneshot_and_wait_on_multiple_threads(1000);
+}
+
+#[test]
+fn drop_on_one_task_ok() {
+    let (tx, rx) = oneshot::channel::<u32>();
+    let f1 = rx.shared();
+    let f2 = f1.clone();
+
+    let (tx2, rx2) = oneshot::channel::<u32>();
+
+    let t1 = thread::spawn(|| {
+        let f = f1.map_err(|_| ()).map(|x| *x).select(rx2.map_err(|_| ()));
+        drop(f.wait());
+    });
+
+    let (tx3, rx3) = oneshot::channel::<u32>();
+
+    let t2 = thread::spawn(|| {
+        let _ = f2.map(|x| tx3.complete(*x)).map_err(|_| ()).wait();
+    });
+
+    tx2.complete(11); // cancel `f1`
+    t1.join().unwrap();
+
+    tx.complete(42); // Should cause `f2` and then `rx3` to get resolved.
+    let result = rx3.wait().unwrap();
+    assert_eq!(result, 42);
+    t2.join().unwrap();
+}
+
+#[test]
+fn drop_in_poll() {
+    let slot = Arc::new(RefCellSend(RefCell::new(None)));
+    let slot2 = slot.clone();
+    let future = future::poll_fn(move || {
+        drop(slot2.0.borrow_mut().take().unwrap());
+        Ok::<_, ()>(1.into())
+    }).shared();
+    let future2 = Box::new(future.clone()) as Box<Future<Item=_, Error=_>>;
+    *slot.0.borrow_mut() = Some(future2);
+    assert_eq!(*future.wait().unwrap(), 1);
+}
+
+struct RefCellSend<T>(RefCell<T>);
+
+unsafe impl<T> Send for RefCellSend<T> {}
+unsafe impl<T> Sync for RefCellSend<T> {}
+
+#[test]
+fn error_is_shared() {
+    let f1 = future::err::<u32, u32>(3).shared();
+    let f2 = f1.clone();
+    assert_eq!(*f1.wait().unwrap_err(), 3);
+    assert_eq!(*f2.wait().unwrap_err(), 3);
+}
+
+#[test]
+fn polled_once() {
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Future for Counted {
+        type Item = u32;
+        type Error = ();
+
+        fn poll(&mut self) -> Poll<u32, ()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(Async::Ready(2))
+        }
+    }
+
+    let polls = Arc::new(AtomicUsize::new(0));
+    let f1 = Counted(polls.clone()).shared();
+    let f2 = f1.clone();
+    let f3 = f1.clone();
+    assert_eq!(*f1.wait().unwrap(), 2);
+    assert_eq!(*f2.wait().unwrap(), 2);
+    assert_eq!(*f3.wait().unwrap(), 2);
+    assert_eq!(polls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn all_waiters_unparked() {
+    struct Count(AtomicUsize);
+
+    impl Unpark for Count {
+        fn unpark(&self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let (tx, rx) = oneshot::channel::<u32>();
+    let f1 = rx.shared();
+    let f2 = f1.clone();
+
+    let mut s1 = executor::spawn(f1);
+    let mut s2 = executor::spawn(f2);
+    let c1 = Arc::new(Count(AtomicUsize::new(0)));
+    let c2 = Arc::new(Count(AtomicUsize::new(0)));
+    assert!(s1.poll_future(c1.clone()).unwrap().is_not_ready());
+    assert!(s2.poll_future(c2.clone()).unwrap().is_not_ready());
+
+    tx.complete(5);
+    assert_eq!(c1.0.load(Ordering::SeqCst), 1);
+    assert_eq!(c2.0.load(Ordering::SeqCst), 1);
+
+    match s2.poll_future(unpark_panic()) {
+        Ok(Async::Ready(ref n)) => assert_eq!(**n, 5),
+        _ => panic!("expected shared future to be ready"),
+    }
+    match s1.poll_future(unpark_panic()) {
+        Ok(Async::Ready(ref n)) => assert_eq!(**n, 5),
+        _ => panic!("expected shared future to be ready"),
+    }
+}
+
+#[test]
+fn repoll_on_unpark_during_poll() {
+    let mut first = true;
+    let f = future::poll_fn(move || -> Poll<u32, ()> {
+        if first {
+            first = false;
+            task::park().unpark();
+            Ok(Async::NotReady)
+        } else {
+            Ok(Async::Ready(3))
+        }
+    }).shared();
+
+    let mut s = executor::spawn(f);
+    match s.poll_future(unpark_noop()) {
+        Ok(Async::Ready(ref n)) => assert_eq!(**n, 3),
+        _ => panic!("expected shared future to be ready"),
+    }
+}
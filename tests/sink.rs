@@ -9,7 +9,7 @@ use std::sync::atomic::{Ordering, AtomicBool};
 
 use futures::{Poll, Async, Future, AsyncSink, StartSend};
 use futures::future::ok;
-use futures::stream;
+use futures::stream::{self, Stream};
 use futures::sync::{oneshot, mpsc};
 use futures::task::{self, Task};
 use futures::executor::{self, Unpark};
@@ -137,7 +137,7 @@ fn with_flush() {
     let mut block = rx.boxed();
     let mut sink = Vec::new().with(|elem| {
         mem::replace(&mut block, ok(()).boxed())
-            .map(move |_| elem + 1).map_err(|_| panic!())
+            .map(move |_| elem + 1).map_err::<_, ()>(|_| panic!())
     });
 
     assert_eq!(sink.start_send(0), Ok(AsyncSink::Ready));
@@ -331,3 +331,247 @@ fn buffer() {
         _ => panic!()
     }
 }
+
+#[test]
+// test simple use of with_map to change data
+fn with_map() {
+    let sink = Vec::new().with_map(|item: i32| item * 2);
+    let sink = sink.send(0).wait().unwrap();
+    let sink = sink.send(1).wait().unwrap();
+    let sink = sink.send(2).wait().unwrap();
+    assert_eq!(sink.get_ref(), &[0, 2, 4]);
+}
+
+#[test]
+// test that with_map holds on to a mapped item the underlying sink rejected
+fn with_map_buffers_rejected_item() {
+    let (sink, allow) = manual_allow::<i32>();
+    let sink = sink.with_map(|item: i32| item + 1);
+
+    let flag = Flag::new();
+    let mut task = executor::spawn(sink.send(0));
+    assert!(task.poll_future(flag.clone()).unwrap().is_not_ready());
+    allow.start();
+    assert!(flag.get());
+    match task.poll_future(flag.clone()).unwrap() {
+        Async::Ready(sink) => assert_eq!(sink.get_ref().data, vec![1]),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn with_filter() {
+    let sink = Vec::new().with_filter(|item: &i32| *item % 2 == 0);
+    let (sink, _) = sink.send_all(stream::iter((0..6).map(Ok))).wait().unwrap();
+    assert_eq!(sink.get_ref(), &[0, 2, 4]);
+}
+
+#[test]
+// test that with_filter runs its predicate once per item, even if the
+// underlying sink rejects the item at first
+fn with_filter_buffers_rejected_item() {
+    let (sink, allow) = manual_allow::<i32>();
+    let calls = Rc::new(Cell::new(0));
+    let calls2 = calls.clone();
+    let sink = sink.with_filter(move |_: &i32| {
+        calls2.set(calls2.get() + 1);
+        true
+    });
+
+    let flag = Flag::new();
+    let mut task = executor::spawn(sink.send(0));
+    assert!(task.poll_future(flag.clone()).unwrap().is_not_ready());
+    allow.start();
+    assert!(flag.get());
+    match task.poll_future(flag.clone()).unwrap() {
+        Async::Ready(sink) => assert_eq!(sink.get_ref().data, vec![0]),
+        _ => panic!()
+    }
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn with_filter_map() {
+    let sink = Vec::new().with_filter_map(|item: i32| {
+        if item % 2 == 0 { Some(item * 10) } else { None }
+    });
+    let (sink, _) = sink.send_all(stream::iter((0..6).map(Ok))).wait().unwrap();
+    assert_eq!(sink.get_ref(), &[0, 20, 40]);
+}
+
+#[test]
+fn with_flat_map() {
+    let sink = Vec::new().with_flat_map(|item: usize| {
+        stream::iter(vec![item; item].into_iter().map(Ok))
+    });
+    let (sink, _) = sink.send_all(stream::iter((0..4).map(Ok))).wait().unwrap();
+    assert_eq!(sink.get_ref(), &[1, 2, 2, 3, 3, 3]);
+}
+
+#[test]
+// test that with_flat_map waits for the underlying sink to accept each item
+// of the stream before accepting the next value
+fn with_flat_map_blocks() {
+    let (sink, allow) = manual_allow::<i32>();
+    let mut sink = sink.with_flat_map(|item: i32| {
+        stream::iter(vec![Ok(item), Ok(item + 1)])
+    });
+
+    let flag = Flag::new();
+    let mut task = executor::spawn(futures::future::lazy(move || {
+        assert_eq!(sink.start_send(0), Ok(AsyncSink::Ready));
+        assert_eq!(sink.start_send(2), Ok(AsyncSink::NotReady(2)));
+        Ok::<_, ()>(sink)
+    }));
+    let sink = match task.poll_future(flag.clone()).unwrap() {
+        Async::Ready(sink) => sink,
+        _ => panic!()
+    };
+
+    allow.start();
+    assert!(flag.get());
+    let sink = sink.send(2).wait().unwrap();
+    assert_eq!(sink.get_ref().data, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn sink_map_err() {
+    let (tx, rx) = mpsc::channel::<i32>(1);
+    drop(rx);
+    let sink = tx.sink_map_err(|_| "disconnected");
+    assert_eq!(sink.send(0).wait().err(), Some("disconnected"));
+
+    let sink = Vec::new().sink_map_err(|()| 5);
+    assert_eq!(sink.send(1).wait().ok().unwrap().get_ref(), &[1]);
+}
+
+// Records every value it's sent, and whether it has been closed
+struct ManualClose<T> {
+    data: Vec<T>,
+    closed: bool,
+}
+
+impl<T> ManualClose<T> {
+    fn new() -> ManualClose<T> {
+        ManualClose {
+            data: Vec::new(),
+            closed: false,
+        }
+    }
+}
+
+impl<T> Sink for ManualClose<T> {
+    type SinkItem = T;
+    type SinkError = ();
+
+    fn start_send(&mut self, item: T) -> StartSend<T, ()> {
+        assert!(!self.closed);
+        self.data.push(item);
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), ()> {
+        Ok(Async::Ready(()))
+    }
+
+    fn close(&mut self) -> Poll<(), ()> {
+        self.closed = true;
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn close_default_flushes() {
+    let mut sink = ManualFlush::new();
+    assert_eq!(sink.start_send(Some(0)).unwrap(), AsyncSink::Ready);
+
+    let flag = Flag::new();
+    let mut task = executor::spawn(close(sink));
+    assert!(task.poll_future(flag.clone()).unwrap().is_not_ready());
+    assert_eq!(task.get_mut().get_mut().force_flush(), vec![0]);
+    assert!(flag.get());
+    assert!(task.poll_future(flag.clone()).unwrap().is_ready());
+}
+
+#[test]
+// test that closing a buffer sends its buffered values before closing the
+// underlying sink
+fn close_buffer() {
+    let mut sink = ManualClose::new().buffer(2);
+    let flag = Flag::new();
+    executor::spawn(futures::future::lazy(|| {
+        assert_eq!(sink.start_send(0), Ok(AsyncSink::Ready));
+        assert_eq!(sink.start_send(1), Ok(AsyncSink::Ready));
+        Ok::<(), ()>(())
+    })).poll_future(flag).unwrap();
+    assert!(!sink.get_ref().closed);
+
+    let sink = close(sink).wait().unwrap();
+    assert_eq!(sink.get_ref().data, vec![0, 1]);
+    assert!(sink.get_ref().closed);
+}
+
+#[test]
+fn close_propagates_through_adapters() {
+    let sink = ManualClose::new()
+        .with(|x: i32| -> Result<i32, ()> { Ok(x + 1) })
+        .with_map(|x: i32| x * 2)
+        .with_filter(|_: &i32| true)
+        .with_filter_map(Some)
+        .sink_map_err(|()| ());
+    let sink = close(sink).wait().unwrap();
+    assert!(sink.get_ref().get_ref().get_ref().get_ref().get_ref().closed);
+}
+
+#[test]
+fn send_all_closes() {
+    let sink = ManualClose::new();
+    let (sink, _) = sink.send_all(stream::iter(vec![Ok(0), Ok(1)])).wait().unwrap();
+    assert_eq!(sink.data, vec![0, 1]);
+    assert!(sink.closed);
+}
+
+#[test]
+fn forward_closes() {
+    let sink = ManualClose::new();
+    let (_, sink) = stream::iter(vec![Ok::<_, ()>(0), Ok(1)]).forward(sink).wait().unwrap();
+    assert_eq!(sink.data, vec![0, 1]);
+    assert!(sink.closed);
+}
+
+#[test]
+fn fanout_smoke() {
+    let sink = Vec::new().fanout(Vec::new());
+    let (sink, _) = sink.send_all(stream::iter(vec![Ok(1), Ok(2), Ok(3)])).wait().unwrap();
+    let (left, right) = sink.into_inner();
+    assert_eq!(left, vec![1, 2, 3]);
+    assert_eq!(right, vec![1, 2, 3]);
+}
+
+#[test]
+// test that fanout holds on to an item only one side has accepted, and
+// rejects further items until the other side catches up
+fn fanout_backpressure() {
+    let (right, allow) = manual_allow::<i32>();
+    let mut sink = Vec::new().fanout(right);
+
+    let flag = Flag::new();
+    let mut task = executor::spawn(futures::future::lazy(move || {
+        assert_eq!(sink.start_send(0), Ok(AsyncSink::Ready));
+        assert_eq!(sink.start_send(1), Ok(AsyncSink::NotReady(1)));
+        assert!(sink.poll_complete().unwrap().is_not_ready());
+        Ok::<_, ()>(sink)
+    }));
+    let sink = match task.poll_future(flag.clone()).unwrap() {
+        Async::Ready(sink) => sink,
+        _ => panic!()
+    };
+    assert_eq!(sink.get_ref().0, &vec![0]);
+    assert!(sink.get_ref().1.data.is_empty());
+
+    allow.start();
+    assert!(flag.get());
+    let sink = sink.send(1).wait().unwrap();
+    assert_eq!(sink.get_ref().0, &vec![0, 1]);
+    assert_eq!(sink.get_ref().1.data, vec![0, 1]);
+}
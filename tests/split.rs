@@ -39,3 +39,19 @@ fn test_split() {
     }
     assert_eq!(dest, vec![10, 20, 30]);
 }
+
+#[test]
+fn test_split_reunite() {
+    let (sink, stream) = Join(iter(vec![Ok::<_, ()>(10)]), Vec::<i32>::new()).split();
+    let (sink2, stream2) = Join(iter(vec![Ok::<_, ()>(20)]), Vec::new()).split();
+
+    let err = stream.reunite(sink2).err().unwrap();
+    let (sink2, stream) = (err.0, err.1);
+    let err = sink.reunite(stream2).err().unwrap();
+    let (sink, stream2) = (err.0, err.1);
+
+    let j = stream.reunite(sink).ok().unwrap();
+    assert_eq!(j.0.collect().wait(), Ok(vec![10]));
+    let j2 = sink2.reunite(stream2).ok().unwrap();
+    assert_eq!(j2.0.collect().wait(), Ok(vec![20]));
+}
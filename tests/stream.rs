@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate futures;
 
-use futures::{Poll, Future, Stream, Sink};
+use futures::{Poll, Future, Stream, Sink, Never};
 use futures::executor;
 use futures::future::{ok, err};
 use futures::stream::{iter, Peekable, BoxStream};
@@ -131,6 +131,102 @@ fn take_passes_errors_through() {
     assert_eq!(s.next(), None);
 }
 
+#[test]
+fn take_while() {
+    assert_done(|| list().take_while(|e| Ok(*e < 3)).collect(),
+                Ok(vec![1, 2]));
+    assert_done(|| err_list().take_while(|e| Ok(*e < 5)).collect(), Err(3));
+
+    // Once the predicate fails the stream is done, even if it would pass
+    // again later.
+    let mut s = iter(vec![Ok::<i32, ()>(1), Ok(5), Ok(2)])
+        .take_while(|e| ok(*e < 3))
+        .wait();
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), None);
+    assert_eq!(s.next(), None);
+}
+
+#[test]
+fn take_while_predicate_error() {
+    let mut s = list().take_while(|e| if *e == 2 { Err(7) } else { Ok(true) }).wait();
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), Some(Err(7)));
+    assert_eq!(s.next(), Some(Ok(3)));
+    assert_eq!(s.next(), None);
+}
+
+#[test]
+fn scan() {
+    assert_done(|| list().scan(0, |sum, e| {
+        *sum += e;
+        ok(Some(*sum))
+    }).collect(), Ok(vec![1, 3, 6]));
+
+    // Returning `None` ends the stream early.
+    assert_done(|| list().scan((), |_, e| {
+        ok(if e < 3 { Some(e * 10) } else { None })
+    }).collect(), Ok(vec![10, 20]));
+
+    assert_done(|| err_list().scan(0, |_, e| ok(Some(e))).collect(), Err(3));
+}
+
+#[test]
+fn inspect() {
+    let mut seen = Vec::new();
+    assert_eq!(list().inspect(|e| seen.push(*e)).map(|e| e + 1).collect().wait(),
+               Ok(vec![2, 3, 4]));
+    assert_eq!(seen, vec![1, 2, 3]);
+
+    assert_done(|| err_list().inspect(|_| ()).collect(), Err(3));
+}
+
+#[test]
+fn chain() {
+    assert_done(|| list().chain(list()).collect(),
+                Ok(vec![1, 2, 3, 1, 2, 3]));
+    assert_done(|| err_list().chain(list()).collect(), Err(3));
+
+    let mut s = iter(vec![Ok(1), Err(2)]).chain(iter(vec![Ok(3)])).wait();
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), Some(Err(2)));
+    assert_eq!(s.next(), Some(Ok(3)));
+    assert_eq!(s.next(), None);
+}
+
+#[test]
+fn results() {
+    assert_done(|| err_list().results::<Never>().collect(),
+                Ok(vec![Ok(1), Ok(2), Err(3)]));
+
+    // Errors no longer stop consumers like `fold`.
+    let s = iter(vec![Ok(1), Err(2), Ok(3), Err(4)]).results::<Never>();
+    assert_done(|| s.fold(0, |sum, r| ok::<_, Never>(sum + r.unwrap_or(0))),
+                Ok(4));
+}
+
+#[test]
+fn forward_results() {
+    // Errors are forwarded into the sink as items, rather than stopping it.
+    let (tx, rx) = mpsc::unbounded();
+    let s = iter(vec![Ok(1), Err(2), Ok(3)]).results::<mpsc::SendError<Result<i32, u32>>>();
+    let (_, tx) = s.forward(tx).wait().unwrap();
+    drop(tx);
+    assert_done(|| rx.collect(), Ok(vec![Ok(1), Err(2), Ok(3)]));
+}
+
+#[test]
+fn flatten_results() {
+    let mut s = iter(vec![Ok(1), Err(2), Ok(3)]).results().flatten_results().wait();
+    assert_eq!(s.next(), Some(Ok(1)));
+    assert_eq!(s.next(), Some(Err(2)));
+    assert_eq!(s.next(), Some(Ok(3)));
+    assert_eq!(s.next(), None);
+
+    assert_done(|| list().results().flatten_results().collect(),
+                Ok(vec![1, 2, 3]));
+}
+
 #[test]
 fn peekable() {
     assert_done(|| list().peekable().collect(), Ok(vec![1, 2, 3]));
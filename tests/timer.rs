@@ -0,0 +1,308 @@
+extern crate futures;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use futures::{Async, Future, Stream};
+use futures::executor::{self, Unpark};
+use futures::future;
+use futures::sync::{mpsc, oneshot};
+use futures::timer::{self, Timer, TimeoutError};
+
+mod support;
+use support::*;
+
+struct Count(AtomicUsize);
+
+impl Unpark for Count {
+    fn unpark(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn count() -> Arc<Count> {
+    Arc::new(Count(AtomicUsize::new(0)))
+}
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn mock_delay() {
+    let (timer, clock) = Timer::mock();
+    let start = clock.now();
+    let mut delay = executor::spawn(timer.delay(ms(100)));
+    assert_eq!(delay.get_ref().expires_at(), start + ms(100));
+
+    let c = count();
+    assert!(delay.poll_future(c.clone()).unwrap().is_not_ready());
+    clock.advance(ms(99));
+    assert_eq!(c.0.load(Ordering::SeqCst), 0);
+    assert!(delay.poll_future(c.clone()).unwrap().is_not_ready());
+
+    clock.advance(ms(1));
+    assert_eq!(timer.now(), start + ms(100));
+    assert_eq!(c.0.load(Ordering::SeqCst), 1);
+    assert!(delay.poll_future(unpark_panic()).unwrap().is_ready());
+}
+
+#[test]
+fn delay_expires_at_with_future_in_scope() {
+    // `Future` is imported here, so this checks that none of its combinators
+    // shadow the inherent accessor on an owned `Delay`.
+    let (timer, clock) = Timer::mock();
+    let deadline = timer.now() + ms(5);
+    let delay = timer.delay_until(deadline);
+    assert_eq!(delay.expires_at(), deadline);
+
+    // `deadline` on an owned `Delay` is the `Future` combinator.
+    let mut timeout = executor::spawn(delay.deadline(deadline + ms(5)));
+    clock.advance(ms(5));
+    assert_eq!(timeout.poll_future(unpark_panic()), Ok(Async::Ready(())));
+}
+
+#[test]
+fn mock_delay_in_the_past() {
+    let (timer, clock) = Timer::mock();
+    clock.advance(ms(10));
+    let now = timer.now();
+    assert!(timer.delay_until(now - ms(5)).wait().is_ok());
+    assert!(timer.delay(ms(0)).wait().is_ok());
+}
+
+#[test]
+fn mock_delay_sub_tick() {
+    let (timer, clock) = Timer::mock();
+    let mut delay = executor::spawn(timer.delay(Duration::new(0, 1_500_000)));
+    assert!(delay.poll_future(unpark_noop()).unwrap().is_not_ready());
+
+    // Timeouts never fire early, even if they're between ticks.
+    clock.advance(ms(1));
+    assert!(delay.poll_future(unpark_noop()).unwrap().is_not_ready());
+    clock.advance(ms(1));
+    assert!(delay.poll_future(unpark_panic()).unwrap().is_ready());
+}
+
+#[test]
+fn mock_many_rotations() {
+    let (timer, clock) = Timer::mock();
+    let delays = (1..50).map(|i| executor::spawn(timer.delay(ms(i * 97))))
+                        .collect::<Vec<_>>();
+    let mut delays = delays.into_iter().map(|d| (d, count())).collect::<Vec<_>>();
+    for &mut (ref mut d, ref c) in delays.iter_mut() {
+        assert!(d.poll_future(c.clone()).unwrap().is_not_ready());
+    }
+
+    for (i, &mut (ref mut d, ref c)) in delays.iter_mut().enumerate() {
+        clock.advance(ms(97));
+        assert_eq!(c.0.load(Ordering::SeqCst), 1, "delay {} didn't fire", i);
+        assert!(d.poll_future(unpark_panic()).unwrap().is_ready());
+    }
+}
+
+#[test]
+fn mock_large_jump() {
+    let (timer, clock) = Timer::mock();
+    let mut a = executor::spawn(timer.delay(Duration::from_secs(3600)));
+    let mut b = executor::spawn(timer.delay(Duration::from_secs(7200)));
+    assert!(a.poll_future(unpark_noop()).unwrap().is_not_ready());
+    assert!(b.poll_future(unpark_noop()).unwrap().is_not_ready());
+
+    clock.advance(Duration::from_secs(3601));
+    assert!(a.poll_future(unpark_panic()).unwrap().is_ready());
+    assert!(b.poll_future(unpark_noop()).unwrap().is_not_ready());
+    clock.advance(Duration::from_secs(3600));
+    assert!(b.poll_future(unpark_panic()).unwrap().is_ready());
+}
+
+#[test]
+fn mock_reset() {
+    let (timer, clock) = Timer::mock();
+    let mut delay = executor::spawn(timer.delay(ms(10)));
+    assert!(delay.poll_future(unpark_noop()).unwrap().is_not_ready());
+
+    let later = timer.now() + ms(20);
+    delay.get_mut().reset(later);
+    clock.advance(ms(10));
+    assert!(delay.poll_future(unpark_noop()).unwrap().is_not_ready());
+    clock.advance(ms(10));
+    assert!(delay.poll_future(unpark_panic()).unwrap().is_ready());
+}
+
+#[test]
+fn mock_interval() {
+    let (timer, clock) = Timer::mock();
+    let mut interval = executor::spawn(timer.interval(ms(10)));
+    assert_eq!(interval.get_ref().period(), ms(10));
+
+    for _ in 0..3 {
+        let c = count();
+        assert!(interval.poll_stream(c.clone()).unwrap().is_not_ready());
+        clock.advance(ms(10));
+        assert_eq!(c.0.load(Ordering::SeqCst), 1);
+        match interval.poll_stream(unpark_panic()) {
+            Ok(Async::Ready(Some(()))) => {}
+            _ => panic!("interval should have ticked"),
+        }
+    }
+
+    // Missed ticks are caught up on right away.
+    clock.advance(ms(25));
+    assert!(interval.poll_stream(unpark_panic()).unwrap().is_ready());
+    assert!(interval.poll_stream(unpark_panic()).unwrap().is_ready());
+    assert!(interval.poll_stream(unpark_noop()).unwrap().is_not_ready());
+}
+
+#[test]
+#[should_panic]
+fn interval_zero_period() {
+    let (timer, _clock) = Timer::mock();
+    drop(timer.interval(ms(0)));
+}
+
+#[test]
+fn mock_timeout_completes() {
+    let (timer, clock) = Timer::mock();
+    let (tx, rx) = oneshot::channel::<u32>();
+    let mut timeout = executor::spawn(timer.timeout(rx, ms(100)));
+    assert!(timeout.poll_future(unpark_noop()).unwrap().is_not_ready());
+
+    clock.advance(ms(50));
+    tx.complete(3);
+    assert_eq!(timeout.poll_future(unpark_panic()), Ok(Async::Ready(3)));
+
+    let timeout = timer.timeout(future::err::<(), u32>(4), ms(100));
+    assert_eq!(timeout.wait(), Err(TimeoutError::Inner(4)));
+}
+
+#[test]
+fn mock_timeout_elapses() {
+    let (timer, clock) = Timer::mock();
+    let (tx, rx) = oneshot::channel::<u32>();
+    let mut timeout = executor::spawn(timer.timeout(rx, ms(100)));
+    let c = count();
+    assert!(timeout.poll_future(c.clone()).unwrap().is_not_ready());
+    assert!(timeout.get_ref().get_ref().is_some());
+
+    clock.advance(ms(100));
+    assert_eq!(c.0.load(Ordering::SeqCst), 1);
+    let err = timeout.poll_future(unpark_panic()).err().unwrap();
+    assert!(err.is_elapsed());
+    assert_eq!(err.into_inner(), None);
+
+    // The inner future is dropped as soon as the timeout elapses.
+    assert!(timeout.get_ref().get_ref().is_none());
+    assert!(tx.is_canceled());
+}
+
+#[test]
+fn mock_deadline() {
+    let (timer, clock) = Timer::mock();
+    let deadline = timer.now() + ms(20);
+    let mut timeout = executor::spawn(timer.deadline(future::empty::<(), ()>(), deadline));
+    clock.advance(ms(19));
+    assert!(timeout.poll_future(unpark_noop()).unwrap().is_not_ready());
+    clock.advance(ms(1));
+    assert_eq!(timeout.poll_future(unpark_panic()), Err(TimeoutError::Elapsed));
+
+    let timeout = timer.deadline(future::ok::<u32, ()>(1), timer.now() - ms(5));
+    assert_eq!(timeout.wait(), Ok(1));
+}
+
+#[test]
+fn mock_stream_timeout() {
+    let (timer, clock) = Timer::mock();
+    let (mut tx, rx) = mpsc::unbounded::<u32>();
+    let mut stream = executor::spawn(timer.timeout_stream(rx, ms(100)));
+    assert!(stream.poll_stream(unpark_noop()).unwrap().is_not_ready());
+
+    // Each item restarts the timeout.
+    clock.advance(ms(60));
+    tx.send(1).unwrap();
+    assert_eq!(stream.poll_stream(unpark_noop()), Ok(Async::Ready(Some(1))));
+    clock.advance(ms(60));
+    assert!(stream.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    clock.advance(ms(40));
+    assert_eq!(stream.poll_stream(unpark_noop()), Err(TimeoutError::Elapsed));
+
+    // The stream can keep going after it has been idle.
+    assert!(stream.poll_stream(unpark_noop()).unwrap().is_not_ready());
+    tx.send(2).unwrap();
+    drop(tx);
+    assert_eq!(stream.poll_stream(unpark_noop()), Ok(Async::Ready(Some(2))));
+    assert_eq!(stream.poll_stream(unpark_noop()), Ok(Async::Ready(None)));
+}
+
+#[test]
+fn real_timeout() {
+    let start = Instant::now();
+    let res = future::empty::<(), ()>().timeout(ms(20)).wait();
+    assert_eq!(res, Err(TimeoutError::Elapsed));
+    assert!(start.elapsed() >= ms(20));
+
+    let deadline = Instant::now() + ms(1000);
+    assert_eq!(future::ok::<u32, ()>(2).deadline(deadline).wait(), Ok(2));
+
+    let items = timer::interval(ms(5)).map_err(|e| match e {})
+                                      .timeout(ms(1000))
+                                      .take(2)
+                                      .collect()
+                                      .wait();
+    assert_eq!(items, Ok(vec![(), ()]));
+}
+
+#[test]
+fn real_delay() {
+    let start = Instant::now();
+    timer::delay(ms(20)).wait().unwrap();
+    assert!(start.elapsed() >= ms(20));
+
+    let timer = Timer::new();
+    let start = Instant::now();
+    timer.delay(ms(20)).wait().unwrap();
+    assert!(start.elapsed() >= ms(20));
+
+    let timer = Timer::default();
+    let start = Instant::now();
+    timer.delay(ms(20)).wait().unwrap();
+    assert!(start.elapsed() >= ms(20));
+}
+
+#[test]
+fn real_delay_registered_after_later_one() {
+    // The timer thread sleeps until the long delay is due, so registering
+    // the short one has to wake it up early.
+    let timer = Timer::new();
+    let long = timer.delay(Duration::from_secs(60));
+    thread::sleep(ms(10));
+    let start = Instant::now();
+    timer.delay(ms(20)).wait().unwrap();
+    assert!(start.elapsed() >= ms(20));
+    assert!(start.elapsed() < Duration::from_secs(30));
+    drop(long);
+}
+
+#[test]
+fn real_delays_from_threads() {
+    let threads = (0..10).map(|i| {
+        thread::spawn(move || {
+            let start = Instant::now();
+            timer::delay_until(start + ms(i * 5)).wait().unwrap();
+            assert!(start.elapsed() >= ms(i * 5));
+        })
+    }).collect::<Vec<_>>();
+    for t in threads {
+        t.join().unwrap();
+    }
+}
+
+#[test]
+fn real_interval() {
+    let start = Instant::now();
+    let ticks = timer::interval(ms(5)).take(4).collect().wait().unwrap();
+    assert_eq!(ticks.len(), 4);
+    assert!(start.elapsed() >= ms(20));
+}
@@ -0,0 +1,99 @@
+extern crate futures;
+
+use std::thread;
+
+use futures::{Future, Stream};
+use futures::executor;
+use futures::sync::watch;
+
+mod support;
+use support::*;
+
+fn is_send<T: Send>() {}
+
+#[test]
+fn bounds() {
+    is_send::<watch::Sender<i32>>();
+    is_send::<watch::Receiver<i32>>();
+}
+
+#[test]
+fn only_latest_value_is_seen() {
+    let (mut tx, mut rx) = watch::channel(0);
+    assert_eq!(*rx.borrow(), 0);
+    sassert_empty(&mut rx);
+
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert_eq!(*tx.borrow(), 2);
+    sassert_next(&mut rx, 2);
+    sassert_empty(&mut rx);
+    assert_eq!(*rx.borrow(), 2);
+
+    tx.send(3).unwrap();
+    drop(tx);
+    sassert_next(&mut rx, 3);
+    sassert_done(&mut rx);
+}
+
+#[test]
+fn clones_track_versions_separately() {
+    let (mut tx, mut rx1) = watch::channel("a");
+    tx.send("b").unwrap();
+
+    let mut rx2 = rx1.clone();
+    sassert_next(&mut rx1, "b");
+    sassert_empty(&mut rx1);
+    sassert_next(&mut rx2, "b");
+
+    let mut rx3 = rx1.clone();
+    sassert_empty(&mut rx3);
+    tx.send("c").unwrap();
+    sassert_next(&mut rx1, "c");
+    sassert_next(&mut rx2, "c");
+    sassert_next(&mut rx3, "c");
+}
+
+#[test]
+fn send_without_receivers() {
+    let (mut tx, rx) = watch::channel(0);
+    let rx2 = rx.clone();
+    drop(rx);
+    assert!(tx.send(1).is_ok());
+    drop(rx2);
+    assert_eq!(tx.send(2).unwrap_err().into_inner(), 2);
+}
+
+#[test]
+fn sender_wakes_receivers() {
+    let (mut tx, rx) = watch::channel(0u32);
+    let mut a = executor::spawn(rx.clone().into_future());
+    let mut b = executor::spawn(rx.into_future());
+    assert!(a.poll_future(unpark_noop()).ok().unwrap().is_not_ready());
+    assert!(b.poll_future(unpark_noop()).ok().unwrap().is_not_ready());
+
+    let t = thread::spawn(move || {
+        tx.send(7).unwrap();
+        tx
+    });
+    assert_eq!(a.wait_future().ok().unwrap().0, Some(7));
+    assert_eq!(b.wait_future().ok().unwrap().0, Some(7));
+    drop(t.join().unwrap());
+}
+
+#[test]
+fn many_updates() {
+    let (mut tx, rx) = watch::channel(0);
+    let t = thread::spawn(move || {
+        for i in 1..1001 {
+            tx.send(i).unwrap();
+        }
+    });
+
+    // Versions may be skipped, but they're always seen in order and the last
+    // one is never missed.
+    let seen = rx.collect().wait().unwrap();
+    t.join().unwrap();
+    assert!(seen.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(seen.last(), Some(&1000));
+}